@@ -0,0 +1,153 @@
+import {
+    init,
+    ActiveEvents,
+    Collider,
+    ColliderDesc,
+    CollisionEventFlags,
+    EventQueue,
+    JointData,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+describe("3d/EventQueue", () => {
+    let world: World;
+    let eventQueue: EventQueue;
+    let ground: Collider;
+    let box: Collider;
+    let body: RigidBody;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        eventQueue = new EventQueue(false);
+
+        ground = world.createCollider(ColliderDesc.cuboid(10, 0.1, 10));
+        body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0, 0.7, 0),
+        );
+        box = world.createCollider(
+            ColliderDesc.cuboid(0.5, 0.5, 0.5)
+                .setActiveEvents(
+                    ActiveEvents.COLLISION_EVENTS |
+                        ActiveEvents.CONTACT_FORCE_EVENTS,
+                )
+                .setContactForceEventThreshold(0),
+            body,
+        );
+    });
+
+    afterEach(() => {
+        eventQueue.free();
+        world.free();
+    });
+
+    test("collision events packed", () => {
+        const stride = EventQueue.collisionEventStride();
+        for (let i = 0; i < 30; i++) {
+            world.step(eventQueue);
+        }
+
+        const events = eventQueue.drainCollisionEventsPacked();
+        expect(events.length).toBe(stride);
+        expect([events[0], events[1]].sort()).toEqual(
+            [ground.handle, box.handle].sort(),
+        );
+        expect(events[2]).toBe(1);
+        expect(events[3]).toBe(CollisionEventFlags.NONE);
+        expect(eventQueue.drainCollisionEventsPacked().length).toBe(0);
+
+        world.removeCollider(box, true);
+        world.step(eventQueue);
+
+        const stopped = eventQueue.drainCollisionEventsPacked();
+        expect(stopped.length).toBe(stride);
+        expect(stopped[2]).toBe(0);
+        expect(stopped[3] & CollisionEventFlags.REMOVED).toBeTruthy();
+    });
+
+    test("collision event objects", () => {
+        for (let i = 0; i < 30; i++) {
+            world.step(eventQueue);
+        }
+
+        let numEvents = 0;
+        eventQueue.drainCollisionEventObjects((event) => {
+            expect(event.started()).toBe(true);
+            expect(event.sensor()).toBe(false);
+            expect(event.removed()).toBe(false);
+            numEvents += 1;
+        });
+        expect(numEvents).toBe(1);
+    });
+
+    test("contact force events packed", () => {
+        const stride = EventQueue.contactForceEventStride();
+        for (let i = 0; i < 30; i++) {
+            world.step(eventQueue);
+        }
+
+        const events = eventQueue.drainContactForceEventsPacked();
+        expect(events.length).toBeGreaterThan(0);
+        expect(events.length % stride).toBe(0);
+
+        // The last event is the box resting on the ground.
+        const last = events.subarray(events.length - stride);
+        expect([last[0], last[1]].sort()).toEqual(
+            [ground.handle, box.handle].sort(),
+        );
+        const totalForceMagnitude = last[5];
+        expect(totalForceMagnitude).toBeGreaterThan(0);
+    });
+
+    test("sleep events packed", () => {
+        const stride = EventQueue.sleepEventStride();
+
+        world.step(eventQueue);
+        const wokeUp = eventQueue.drainSleepEventsPacked();
+        expect(Array.from(wokeUp)).toEqual([body.handle, 0]);
+
+        for (let i = 0; i < 600 && !body.isSleeping(); i++) {
+            world.step(eventQueue);
+        }
+        expect(body.isSleeping()).toBe(true);
+
+        const fellAsleep = eventQueue.drainSleepEventsPacked();
+        expect(fellAsleep.length).toBe(stride);
+        expect(Array.from(fellAsleep)).toEqual([body.handle, 1]);
+    });
+
+    test("joint break events packed", () => {
+        const anchor = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(5, 5, 0),
+        );
+        const hanging = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(5, 4, 0),
+        );
+        world.createCollider(ColliderDesc.ball(0.1), hanging);
+        const joint = world.createImpulseJoint(
+            JointData.spherical(new Vector3(0, 0, 0), new Vector3(0, 1, 0)),
+            anchor,
+            hanging,
+            true,
+        );
+        joint.setBreakThresholds(0, Infinity);
+
+        world.step(eventQueue);
+        const events = eventQueue.drainJointBreakEventsPacked();
+        expect(events.length).toBe(EventQueue.jointBreakEventStride());
+        expect(Array.from(events)).toEqual([
+            joint.handle,
+            anchor.handle,
+            hanging.handle,
+        ]);
+        expect(joint.isEnabled()).toBe(false);
+    });
+});
@@ -0,0 +1,117 @@
+import {
+    init,
+    ColliderDesc,
+    EventQueue,
+    ImpulseJoint,
+    JointData,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+describe("3d/ImpulseJoint", () => {
+    const mass = 2.0;
+    const gravity = 9.81;
+    let world: World;
+    let eventQueue: EventQueue;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -gravity, 0));
+        eventQueue = new EventQueue(true);
+    });
+
+    afterEach(() => {
+        eventQueue.free();
+        world.free();
+    });
+
+    // A mass hanging at rest below a fixed body by a spherical joint.
+    function hangingMass(): ImpulseJoint {
+        const anchor = world.createRigidBody(RigidBodyDesc.fixed());
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0, -1, 0),
+        );
+        world.createCollider(ColliderDesc.ball(0.1).setMass(mass), body);
+        const params = JointData.spherical(
+            new Vector3(0, 0, 0),
+            new Vector3(0, 1, 0),
+        );
+        return world.createImpulseJoint(params, anchor, body, true);
+    }
+
+    function breaksWithin(joint: ImpulseJoint, steps: number): boolean {
+        let broken = false;
+        for (let i = 0; i < steps && !broken; i++) {
+            world.step(eventQueue);
+            eventQueue.drainJointBreakEvents((handle) => {
+                expect(handle).toBe(joint.handle);
+                broken = true;
+            });
+        }
+        return broken;
+    }
+
+    test("impulses", () => {
+        const joint = hangingMass();
+        for (let i = 0; i < 10; i++) {
+            world.step(eventQueue);
+        }
+
+        const substepDt =
+            world.integrationParameters.dt /
+            world.integrationParameters.numSolverIterations;
+        const force = Math.abs(joint.linearImpulse().y) / substepDt;
+        expect(force).toBeGreaterThan(mass * gravity * 0.9);
+        expect(force).toBeLessThan(mass * gravity * 1.1);
+    });
+
+    test("does not break below the load", () => {
+        const joint = hangingMass();
+        joint.setBreakThresholds(mass * gravity * 1.25, Infinity);
+
+        expect(breaksWithin(joint, 60)).toBe(false);
+        expect(joint.isEnabled()).toBe(true);
+    });
+
+    test("breaks above the load", () => {
+        const joint = hangingMass();
+        joint.setBreakThresholds(mass * gravity * 0.75, Infinity);
+
+        expect(breaksWithin(joint, 60)).toBe(true);
+        expect(joint.isEnabled()).toBe(false);
+        expect(joint.breakForce()).toBe(Infinity);
+    });
+
+    test("scene round-trip keeps the break thresholds", () => {
+        const joint = hangingMass();
+        joint.setBreakThresholds(mass * gravity * 0.75, Infinity);
+
+        const imported = World.importScene(world.exportScene());
+        const joints = imported.impulseJoints.getAll();
+        expect(joints.length).toBe(1);
+        expect(joints[0].breakForce()).toBeCloseTo(mass * gravity * 0.75);
+        expect(joints[0].breakTorque()).toBe(Infinity);
+        imported.free();
+    });
+
+    test("prefab keeps the break thresholds", () => {
+        const joint = hangingMass();
+        joint.setBreakThresholds(Infinity, 5.0);
+
+        const prefab = world.serializePrefab([
+            joint.body1().handle,
+            joint.body2().handle,
+        ]);
+        const map = world.insertPrefab(prefab);
+        const copy = world.getImpulseJoint(map.impulseJoints.get(joint.handle));
+        expect(copy.handle).not.toBe(joint.handle);
+        expect(copy.breakForce()).toBe(Infinity);
+        expect(copy.breakTorque()).toBeCloseTo(5.0);
+    });
+});
@@ -0,0 +1,107 @@
+import {
+    init,
+    ColliderDesc,
+    InverseKinematicsOptions,
+    JointAxesMask,
+    JointData,
+    RevoluteMultibodyJoint,
+    RigidBody,
+    RigidBodyDesc,
+    Vector2,
+    World,
+} from "../builds/2d-deterministic/pkg";
+
+describe("2d/Multibody", () => {
+    let world: World;
+    let link: RigidBody;
+    let joint: RevoluteMultibodyJoint;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector2(0, 0));
+
+        // A link rotating around its root, at a distance of 1.
+        const root = world.createRigidBody(RigidBodyDesc.fixed());
+        link = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(1, 0),
+        );
+        world.createCollider(ColliderDesc.ball(0.1), link);
+        const params = JointData.revolute(
+            new Vector2(0, 0),
+            new Vector2(-1, 0),
+        );
+        joint = world.createMultibodyJoint(
+            params,
+            root,
+            link,
+            true,
+        ) as RevoluteMultibodyJoint;
+        world.step();
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("motor velocity", () => {
+        joint.configureMotorVelocity(-1.5, 10.0);
+
+        for (let i = 0; i < 10; i++) {
+            world.step();
+        }
+
+        expect(joint.velocities()[0]).toBeCloseTo(-1.5, 2);
+        expect(joint.coords()[0]).toBeLessThan(0);
+    });
+
+    test("inverse kinematics", () => {
+        const options = new InverseKinematicsOptions();
+        expect(options.constrainedAxes).toBe(0b111);
+
+        link.sleep();
+        const displacements = world.multibodyInverseKinematics(
+            joint,
+            new Vector2(0, 1),
+            Math.PI / 2,
+            options,
+        );
+
+        expect(displacements[0]).toBeCloseTo(Math.PI / 2, 2);
+        expect(link.isSleeping()).toBe(false);
+        expect(link.translation().x).toBeCloseTo(0, 2);
+        expect(link.translation().y).toBeCloseTo(1, 2);
+        expect(link.rotation()).toBeCloseTo(Math.PI / 2, 2);
+    });
+
+    test("inverse kinematics of the translation only", () => {
+        const options = new InverseKinematicsOptions();
+        options.constrainedAxes = JointAxesMask.LinX | JointAxesMask.LinY;
+        options.maxIters = 50;
+
+        world.multibodyInverseKinematics(joint, new Vector2(0, -1), 0, options);
+
+        expect(link.translation().x).toBeCloseTo(0, 2);
+        expect(link.translation().y).toBeCloseTo(-1, 2);
+    });
+
+    test("inverse kinematics of the rotation only", () => {
+        const options = new InverseKinematicsOptions();
+        options.constrainedAxes = JointAxesMask.AngX;
+        options.maxIters = 50;
+
+        const displacements = world.multibodyInverseKinematics(
+            joint,
+            new Vector2(5, 5),
+            Math.PI / 2,
+            options,
+            false,
+        );
+
+        expect(displacements[0]).toBeCloseTo(Math.PI / 2, 2);
+    });
+});
@@ -0,0 +1,146 @@
+import {
+    init,
+    ColliderDesc,
+    JointData,
+    RevoluteMultibodyJoint,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+describe("3d/Multibody", () => {
+    let world: World;
+    let link: RigidBody;
+    let joint: RevoluteMultibodyJoint;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, 0, 0));
+
+        // A link rotating around the z axis, at a distance of 1 from its root.
+        const root = world.createRigidBody(RigidBodyDesc.fixed());
+        link = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(1, 0, 0),
+        );
+        world.createCollider(ColliderDesc.ball(0.1), link);
+        const params = JointData.revolute(
+            new Vector3(0, 0, 0),
+            new Vector3(-1, 0, 0),
+            new Vector3(0, 0, 1),
+        );
+        joint = world.createMultibodyJoint(
+            params,
+            root,
+            link,
+            true,
+        ) as RevoluteMultibodyJoint;
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("motor velocity", () => {
+        world.step();
+        joint.configureMotorVelocity(2.0, 10.0);
+
+        for (let i = 0; i < 10; i++) {
+            world.step();
+        }
+
+        expect(joint.velocities()[0]).toBeCloseTo(2.0, 2);
+        expect(joint.coords()[0]).toBeGreaterThan(0);
+    });
+
+    test("motor with limits", () => {
+        world.step();
+        joint.setLimits(-0.5, 0.5);
+        joint.configureMotorVelocity(2.0, 10.0);
+
+        for (let i = 0; i < 120; i++) {
+            world.step();
+        }
+
+        expect(joint.limitsEnabled()).toBe(true);
+        expect(joint.coords()[0]).toBeCloseTo(0.5, 2);
+        expect(joint.velocities()[0]).toBeCloseTo(0.0, 2);
+    });
+
+    test("limit and motor setters wake up the multibody", () => {
+        world.step();
+
+        link.sleep();
+        joint.setLimits(-0.5, 0.5);
+        expect(link.isSleeping()).toBe(false);
+
+        link.sleep();
+        joint.configureMotorVelocity(2.0, 10.0);
+        expect(link.isSleeping()).toBe(false);
+
+        world.step();
+        expect(joint.velocities()[0]).toBeGreaterThan(0);
+    });
+
+    test("inverse kinematics", () => {
+        world.step();
+        const target = new Vector3(0, 1, 0);
+        const s = Math.SQRT1_2;
+        const rotation = {x: 0, y: 0, z: s, w: s};
+
+        const preview = world.multibodyInverseKinematics(
+            joint,
+            target,
+            rotation,
+            undefined,
+            false,
+        );
+        expect(preview.length).toBe(1);
+        expect(preview[0]).toBeCloseTo(Math.PI / 2, 2);
+        expect(link.translation().x).toBeCloseTo(1);
+
+        link.sleep();
+        const displacements = world.multibodyInverseKinematics(
+            joint,
+            target,
+            rotation,
+        );
+        expect(Array.from(displacements)).toEqual(Array.from(preview));
+        expect(link.isSleeping()).toBe(false);
+        expect(link.translation().x).toBeCloseTo(0, 2);
+        expect(link.translation().y).toBeCloseTo(1, 2);
+        expect(joint.coords()[0]).toBeCloseTo(Math.PI / 2, 2);
+    });
+
+    test("generalized impulse", () => {
+        const impulse = new Float32Array([1.0]);
+
+        // The mass matrix is only known after the first step.
+        expect(() =>
+            world.applyMultibodyGeneralizedImpulse(joint, impulse, true),
+        ).toThrow(/singular/);
+
+        world.step();
+        const massMatrix = world.multibodyMassMatrix(joint);
+        expect(massMatrix.length).toBe(1);
+        expect(massMatrix[0]).toBeGreaterThan(0);
+
+        world.applyMultibodyGeneralizedImpulse(joint, impulse, true);
+        expect(joint.generalizedVelocities()[0]).toBeCloseTo(
+            1.0 / massMatrix[0],
+        );
+
+        expect(() =>
+            world.applyMultibodyGeneralizedImpulse(
+                joint,
+                new Float32Array(2),
+                true,
+            ),
+        ).toThrow();
+    });
+});
@@ -0,0 +1,104 @@
+import {
+    init,
+    ActiveHooks,
+    ColliderDesc,
+    ContactModificationContext,
+    EventQueue,
+    PhysicsHooks,
+    RigidBody,
+    RigidBodyDesc,
+    SolverFlags,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+class Hooks implements PhysicsHooks {
+    numCalls = 0;
+
+    constructor(public modify: (context: ContactModificationContext) => void) {}
+
+    filterContactPair(): SolverFlags {
+        return SolverFlags.COMPUTE_IMPULSE;
+    }
+
+    filterIntersectionPair(): boolean {
+        return true;
+    }
+
+    modifySolverContacts(context: ContactModificationContext) {
+        this.numCalls += 1;
+        this.modify(context);
+    }
+}
+
+describe("3d/PhysicsHooks", () => {
+    let world: World;
+    let eventQueue: EventQueue;
+    let ball: RigidBody;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        eventQueue = new EventQueue(true);
+        world.createCollider(
+            ColliderDesc.cuboid(10, 0.1, 10).setActiveHooks(
+                ActiveHooks.MODIFY_SOLVER_CONTACTS,
+            ),
+        );
+        ball = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0, 0.4, 0),
+        );
+        world.createCollider(ColliderDesc.ball(0.25), ball);
+    });
+
+    afterEach(() => {
+        eventQueue.free();
+        world.free();
+    });
+
+    function stepN(hooks: Hooks, n: number) {
+        for (let i = 0; i < n; i++) {
+            world.step(eventQueue, hooks);
+        }
+    }
+
+    test("modifySolverContacts", () => {
+        const hooks = new Hooks(() => {});
+        stepN(hooks, 60);
+
+        expect(hooks.numCalls).toBeGreaterThan(0);
+        expect(ball.translation().y).toBeGreaterThan(0.3);
+    });
+
+    test("clearing the solver contacts", () => {
+        let numSolverContacts = 0;
+        const hooks = new Hooks((context) => {
+            numSolverContacts += context.numSolverContacts();
+            context.clearSolverContacts();
+        });
+        stepN(hooks, 60);
+
+        expect(hooks.numCalls).toBeGreaterThan(0);
+        expect(numSolverContacts).toBeGreaterThan(0);
+        expect(ball.translation().y).toBeLessThan(0);
+    });
+
+    test("throwing hook", () => {
+        const consoleError = jest
+            .spyOn(console, "error")
+            .mockImplementation(() => {});
+        const hooks = new Hooks(() => {
+            throw new Error("hook error");
+        });
+
+        expect(() => stepN(hooks, 60)).not.toThrow();
+        expect(hooks.numCalls).toBeGreaterThan(1);
+        expect(consoleError).toHaveBeenCalled();
+        consoleError.mockRestore();
+    });
+});
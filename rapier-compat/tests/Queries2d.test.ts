@@ -0,0 +1,109 @@
+import {
+    init,
+    Ball,
+    BroadPhase,
+    Collider,
+    ColliderDesc,
+    FeatureType,
+    Vector2,
+    World,
+} from "../builds/2d-deterministic/pkg";
+
+describe("2d/Queries", () => {
+    let world: World;
+    let ground: Collider;
+    let box: Collider;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector2(0, -9.81));
+        ground = world.createCollider(ColliderDesc.cuboid(10, 0.1));
+        box = world.createCollider(
+            ColliderDesc.cuboid(0.5, 0.5).setTranslation(0, 2),
+        );
+        // Update the broad-phase.
+        world.step();
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("castRays", () => {
+        const stride = BroadPhase.rayHitStride();
+        const origins = new Float32Array([0, 5, 5, 5, 50, 5]);
+        const dirs = new Float32Array([0, -1, 0, -1, 0, -1]);
+        const maxTois = new Float32Array([10, 10, 10]);
+        const hits = world.castRays(origins, dirs, maxTois, true);
+
+        expect(stride).toBe(6);
+        expect(hits.length).toBe(3 * stride);
+
+        expect(hits[0]).toBe(box.handle);
+        expect(hits[1]).toBeCloseTo(2.5);
+        expect(hits[2]).toBeCloseTo(0);
+        expect(hits[3]).toBeCloseTo(1);
+        expect(hits[4]).toBe(FeatureType.Face);
+
+        expect(hits[stride]).toBe(ground.handle);
+        expect(hits[stride + 1]).toBeCloseTo(4.9);
+
+        expect(hits[2 * stride]).toBe(-1);
+        expect(hits[2 * stride + 4]).toBe(FeatureType.Unknown);
+    });
+
+    test("castShapes", () => {
+        const stride = BroadPhase.shapeCastHitStride();
+        const shape = new Ball(0.25);
+        const translations = new Float32Array([0, 5, 5, 5, 50, 5]);
+        const rotations = new Float32Array([0, 0, 0]);
+        const velocities = new Float32Array([0, -1, 0, -1, 0, -1]);
+        const hits = world.castShapes(
+            translations,
+            rotations,
+            velocities,
+            shape,
+            0,
+            10,
+            true,
+        );
+
+        expect(stride).toBe(10);
+        expect(hits.length).toBe(3 * stride);
+
+        expect(hits[0]).toBe(box.handle);
+        expect(hits[1]).toBeCloseTo(2.25);
+        expect(hits[stride]).toBe(ground.handle);
+        expect(hits[stride + 1]).toBeCloseTo(4.65);
+        expect(hits[2 * stride]).toBe(-1);
+    });
+
+    test("castShapes rejects mismatched lengths", () => {
+        expect(() =>
+            world.castShapes(
+                new Float32Array([0, 5, 5, 5]),
+                new Float32Array([0]),
+                new Float32Array([0, -1, 0, -1]),
+                new Ball(0.25),
+                0,
+                10,
+                true,
+            ),
+        ).toThrow();
+    });
+
+    test("intersectionWithShapes", () => {
+        const handles = world.intersectionWithShapes(
+            new Float32Array([0, 2, 0, 0, 0, 1]),
+            new Float32Array([0, 0, 0]),
+            new Ball(0.25),
+        );
+
+        expect(Array.from(handles)).toEqual([box.handle, ground.handle, -1]);
+    });
+});
@@ -0,0 +1,152 @@
+import {
+    init,
+    Ball,
+    BroadPhase,
+    Collider,
+    ColliderDesc,
+    FeatureType,
+    Ray,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+describe("3d/Queries", () => {
+    let world: World;
+    let ground: Collider;
+    let box: Collider;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        ground = world.createCollider(ColliderDesc.cuboid(10, 0.1, 10));
+        box = world.createCollider(
+            ColliderDesc.cuboid(0.5, 0.5, 0.5).setTranslation(0, 2, 0),
+        );
+        // Update the broad-phase.
+        world.step();
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("castRays", () => {
+        const stride = BroadPhase.rayHitStride();
+        const origins = new Float32Array([0, 5, 0, 5, 5, 0, 50, 5, 0]);
+        const dirs = new Float32Array([0, -1, 0, 0, -1, 0, 0, -1, 0]);
+        const maxTois = new Float32Array([10, 10, 10]);
+        const hits = world.castRays(origins, dirs, maxTois, true);
+
+        expect(stride).toBe(7);
+        expect(hits.length).toBe(3 * stride);
+
+        expect(hits[0]).toBe(box.handle);
+        expect(hits[1]).toBeCloseTo(2.5);
+        expect(hits[2]).toBeCloseTo(0);
+        expect(hits[3]).toBeCloseTo(1);
+        expect(hits[4]).toBeCloseTo(0);
+        expect(hits[5]).toBe(FeatureType.Face);
+
+        expect(hits[stride]).toBe(ground.handle);
+        expect(hits[stride + 1]).toBeCloseTo(4.9);
+
+        expect(hits[2 * stride]).toBe(-1);
+        expect(hits[2 * stride + 1]).toBe(-1);
+        expect(hits[2 * stride + 5]).toBe(FeatureType.Unknown);
+    });
+
+    test("castRays agrees with castRayAndGetNormal", () => {
+        const stride = BroadPhase.rayHitStride();
+        const origins = new Float32Array([0.2, 5, 0.3]);
+        const dirs = new Float32Array([0, -1, 0]);
+        const maxTois = new Float32Array([10]);
+        const hits = world.castRays(origins, dirs, maxTois, true);
+
+        const ray = new Ray(new Vector3(0.2, 5, 0.3), new Vector3(0, -1, 0));
+        const single = world.castRayAndGetNormal(ray, 10, true);
+        expect(hits.length).toBe(stride);
+        expect(hits[0]).toBe(single.collider.handle);
+        expect(hits[1]).toBe(single.timeOfImpact);
+        expect(hits[2]).toBe(single.normal.x);
+        expect(hits[3]).toBe(single.normal.y);
+        expect(hits[4]).toBe(single.normal.z);
+    });
+
+    test("castShapes", () => {
+        const stride = BroadPhase.shapeCastHitStride();
+        const shape = new Ball(0.25);
+        const translations = new Float32Array([0, 5, 0, 5, 5, 0, 50, 5, 0]);
+        const rotations = new Float32Array([
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1,
+        ]);
+        const velocities = new Float32Array([0, -1, 0, 0, -1, 0, 0, -1, 0]);
+        const hits = world.castShapes(
+            translations,
+            rotations,
+            velocities,
+            shape,
+            0,
+            10,
+            true,
+        );
+
+        expect(stride).toBe(14);
+        expect(hits.length).toBe(3 * stride);
+
+        expect(hits[0]).toBe(box.handle);
+        expect(hits[1]).toBeCloseTo(2.25);
+        expect(hits[stride]).toBe(ground.handle);
+        expect(hits[stride + 1]).toBeCloseTo(4.65);
+        expect(hits[2 * stride]).toBe(-1);
+        expect(hits[2 * stride + 1]).toBe(-1);
+    });
+
+    test("intersectionWithShapes", () => {
+        const shape = new Ball(0.25);
+        const translations = new Float32Array([0, 2, 0, 0, 0, 0, 0, 1, 0]);
+        const rotations = new Float32Array([
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1,
+        ]);
+        const handles = world.intersectionWithShapes(
+            translations,
+            rotations,
+            shape,
+        );
+
+        expect(Array.from(handles)).toEqual([box.handle, ground.handle, -1]);
+    });
+
+    test("batched shape queries reject zero rotations", () => {
+        const shape = new Ball(0.25);
+        const translations = new Float32Array([0, 2, 0]);
+        const rotations = new Float32Array([0, 0, 0, 0]);
+
+        expect(() =>
+            world.intersectionWithShapes(translations, rotations, shape),
+        ).toThrow(/zero quaternion/);
+        expect(() =>
+            world.castShapes(
+                translations,
+                rotations,
+                new Float32Array([0, -1, 0]),
+                shape,
+                0,
+                10,
+                true,
+            ),
+        ).toThrow(/zero quaternion/);
+
+        // The query still works after an error.
+        const handles = world.intersectionWithShapes(
+            translations,
+            new Float32Array([0, 0, 0, 1]),
+            shape,
+        );
+        expect(Array.from(handles)).toEqual([box.handle]);
+    });
+});
@@ -0,0 +1,95 @@
+import {
+    init,
+    RigidBody,
+    RigidBodyDesc,
+    RigidBodyPoses,
+    Vector2,
+    World,
+} from "../builds/2d-deterministic/pkg";
+
+describe("2d/RigidBodySet", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector2(0, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function createBodies(desc: () => RigidBodyDesc, n: number): RigidBody[] {
+        const bodies: RigidBody[] = [];
+        for (let i = 0; i < n; i++) {
+            const body = desc().setTranslation(i, i * 2).setRotation(i * 0.5);
+            bodies.push(world.createRigidBody(body));
+        }
+        return bodies;
+    }
+
+    test("exportPoses", () => {
+        createBodies(() => RigidBodyDesc.dynamic(), 3);
+        const poses = new RigidBodyPoses();
+        world.bodies.exportPoses(poses);
+
+        const stride = RigidBodyPoses.stride();
+        const data = poses.data();
+        expect(stride).toBe(6);
+        expect(poses.len()).toBe(3);
+        expect(data.length).toBe(3 * stride);
+
+        for (let i = 0; i < poses.len(); i++) {
+            const body = world.getRigidBody(RigidBodyPoses.handle(data, i));
+            const entry = data.subarray(i * stride, (i + 1) * stride);
+            const t = body.translation();
+            expect(entry[2]).toBe(t.x);
+            expect(entry[3]).toBe(t.y);
+            expect(entry[4]).toBeCloseTo(body.rotation());
+        }
+
+        poses.free();
+    });
+
+    test("empty exportPoses", () => {
+        const poses = new RigidBodyPoses();
+        world.bodies.exportPoses(poses);
+
+        expect(poses.len()).toBe(0);
+        expect(poses.isEmpty()).toBe(true);
+        expect(poses.data().length).toBe(0);
+        poses.free();
+    });
+
+    test("batch setters", () => {
+        const kinematic = createBodies(
+            () => RigidBodyDesc.kinematicPositionBased(),
+            2,
+        );
+        const handles = new Float64Array(kinematic.map((b) => b.handle));
+
+        world.bodies.setNextKinematicTranslations(
+            handles,
+            new Float32Array([1, 2, 3, 4]),
+        );
+        world.bodies.setNextKinematicRotations(
+            handles,
+            new Float32Array([0.25, -0.5]),
+        );
+        world.step();
+
+        expect(kinematic[0].translation()).toEqual({x: 1, y: 2});
+        expect(kinematic[1].translation()).toEqual({x: 3, y: 4});
+        expect(kinematic[0].rotation()).toBeCloseTo(0.25);
+        expect(kinematic[1].rotation()).toBeCloseTo(-0.5);
+
+        expect(() =>
+            world.bodies.setAngvels(handles, new Float32Array(3), true),
+        ).toThrow();
+    });
+});
@@ -0,0 +1,154 @@
+import {
+    init,
+    RigidBody,
+    RigidBodyDesc,
+    RigidBodyPoses,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+describe("3d/RigidBodySet", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, 0, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function createBodies(desc: () => RigidBodyDesc, n: number): RigidBody[] {
+        const bodies: RigidBody[] = [];
+        for (let i = 0; i < n; i++) {
+            bodies.push(
+                world.createRigidBody(desc().setTranslation(i, i * 2, i * 3)),
+            );
+        }
+        return bodies;
+    }
+
+    test("exportPoses", () => {
+        const bodies = createBodies(() => RigidBodyDesc.dynamic(), 3);
+        bodies[1].setRotation({x: 0, y: 1, z: 0, w: 0}, true);
+        const poses = new RigidBodyPoses();
+        world.bodies.exportPoses(poses);
+
+        const stride = RigidBodyPoses.stride();
+        const data = poses.data();
+        expect(stride).toBe(10);
+        expect(poses.len()).toBe(3);
+        expect(poses.isEmpty()).toBe(false);
+        expect(data.length).toBe(3 * stride);
+
+        for (let i = 0; i < poses.len(); i++) {
+            const body = world.getRigidBody(RigidBodyPoses.handle(data, i));
+            const entry = data.subarray(i * stride, (i + 1) * stride);
+            const t = body.translation();
+            const r = body.rotation();
+            expect(Array.from(entry.subarray(2, 9))).toEqual([
+                t.x,
+                t.y,
+                t.z,
+                r.x,
+                r.y,
+                r.z,
+                r.w,
+            ]);
+        }
+
+        expect(Array.from(poses.view())).toEqual(Array.from(data));
+        poses.free();
+    });
+
+    test("exportPoses of active bodies", () => {
+        const bodies = createBodies(() => RigidBodyDesc.dynamic(), 3);
+        world.createRigidBody(RigidBodyDesc.fixed());
+        world.step();
+        bodies[0].sleep();
+        world.step();
+
+        const poses = new RigidBodyPoses();
+        world.bodies.exportPoses(poses, world.islands);
+        const data = poses.data();
+        const handles: number[] = [];
+        for (let i = 0; i < poses.len(); i++) {
+            handles.push(RigidBodyPoses.handle(data, i));
+        }
+
+        expect(handles.sort()).toEqual(
+            [bodies[1].handle, bodies[2].handle].sort(),
+        );
+        poses.free();
+    });
+
+    test("batch setters", () => {
+        const kinematic = createBodies(
+            () => RigidBodyDesc.kinematicPositionBased(),
+            2,
+        );
+        const dynamic = createBodies(() => RigidBodyDesc.dynamic(), 2);
+        const kinematicHandles = new Float64Array(
+            kinematic.map((b) => b.handle),
+        );
+        const dynamicHandles = new Float64Array(dynamic.map((b) => b.handle));
+
+        world.bodies.setNextKinematicTranslations(
+            kinematicHandles,
+            new Float32Array([1, 2, 3, 4, 5, 6]),
+        );
+        world.bodies.setNextKinematicRotations(
+            kinematicHandles,
+            new Float32Array([0, 0, 0, 1, 0, 1, 0, 0]),
+        );
+        world.bodies.setLinvels(
+            dynamicHandles,
+            new Float32Array([1, 0, 0, 0, 2, 0]),
+            true,
+        );
+        world.bodies.setAngvels(
+            dynamicHandles,
+            new Float32Array([0, 0, 1, 0, 0, 2]),
+            true,
+        );
+        world.step();
+
+        expect(kinematic[0].translation()).toEqual({x: 1, y: 2, z: 3});
+        expect(kinematic[1].translation()).toEqual({x: 4, y: 5, z: 6});
+        expect(kinematic[1].rotation()).toEqual({x: 0, y: 1, z: 0, w: 0});
+        expect(dynamic[0].linvel().x).toBeCloseTo(1);
+        expect(dynamic[1].linvel().y).toBeCloseTo(2);
+        expect(dynamic[0].angvel().z).toBeCloseTo(1);
+        expect(dynamic[1].angvel().z).toBeCloseTo(2);
+    });
+
+    test("batch setters reject mismatched lengths", () => {
+        const bodies = createBodies(() => RigidBodyDesc.dynamic(), 2);
+        const handles = new Float64Array(bodies.map((b) => b.handle));
+
+        expect(() =>
+            world.bodies.setNextKinematicTranslations(
+                handles,
+                new Float32Array(5),
+            ),
+        ).toThrow();
+        expect(() =>
+            world.bodies.setNextKinematicRotations(
+                handles,
+                new Float32Array(4),
+            ),
+        ).toThrow();
+        expect(() =>
+            world.bodies.setLinvels(handles, new Float32Array(3), true),
+        ).toThrow();
+        expect(() =>
+            world.bodies.setAngvels(handles, new Float32Array(7), true),
+        ).toThrow();
+    });
+});
@@ -0,0 +1,153 @@
+import {
+    init,
+    ColliderDesc,
+    DeltaSnapshotBase,
+    JointData,
+    RigidBodyDesc,
+    Vector2,
+    World,
+} from "../builds/2d-deterministic/pkg";
+
+describe("2d/Serialization", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector2(0, -9.81));
+
+        const ground = world.createRigidBody(RigidBodyDesc.fixed());
+        world.createCollider(ColliderDesc.cuboid(10, 0.1), ground);
+
+        for (let i = 0; i < 4; i++) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(0, 0.6 + i * 1.1),
+            );
+            world.createCollider(ColliderDesc.cuboid(0.5, 0.5), body);
+        }
+
+        const pendulumAnchor = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(5, 5),
+        );
+        const pendulum = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(6, 5),
+        );
+        world.createCollider(ColliderDesc.ball(0.2), pendulum);
+        world.createImpulseJoint(
+            JointData.revolute(new Vector2(0, 0), new Vector2(-1, 0)),
+            pendulumAnchor,
+            pendulum,
+            true,
+        );
+
+        for (let i = 0; i < 10; i++) {
+            world.step();
+        }
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function stepN(w: World, n: number) {
+        for (let i = 0; i < n; i++) {
+            w.step();
+        }
+    }
+
+    function bodyStates(w: World) {
+        return w.bodies
+            .getAll()
+            .sort((a, b) => a.handle - b.handle)
+            .map((body) => [
+                body.handle,
+                body.translation(),
+                body.rotation(),
+                body.linvel(),
+                body.angvel(),
+            ]);
+    }
+
+    test("snapshot round-trip", () => {
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+
+        stepN(world, 20);
+        stepN(restored, 20);
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+        restored.free();
+    });
+
+    test("delta snapshot round-trip", () => {
+        const base = new DeltaSnapshotBase(world.takeSnapshot());
+        stepN(world, 5);
+
+        const restored = World.restoreDeltaSnapshot(
+            base,
+            world.takeDeltaSnapshot(base),
+        );
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+
+        stepN(world, 20);
+        stepN(restored, 20);
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+
+        restored.free();
+        base.free();
+    });
+
+    test("checkpoint round-trip", () => {
+        const saved = bodyStates(world);
+        const checkpoint = world.saveCheckpoint();
+        stepN(world, 20);
+        const stepped = bodyStates(world);
+
+        expect(world.restoreCheckpoint(checkpoint)).toBe(true);
+        expect(bodyStates(world)).toEqual(saved);
+
+        stepN(world, 20);
+        expect(bodyStates(world)).toEqual(stepped);
+        checkpoint.free();
+    });
+
+    test("scene round-trip", () => {
+        const scene = world.exportScene();
+        const imported = World.importScene(scene);
+
+        expect(imported.bodies.len()).toBe(world.bodies.len());
+        expect(imported.colliders.len()).toBe(world.colliders.len());
+        expect(imported.impulseJoints.len()).toBe(world.impulseJoints.len());
+        expect(imported.exportScene()).toBe(scene);
+        imported.free();
+    });
+
+    test("scene import rejects invalid scenes", () => {
+        const polyline = {
+            version: 1,
+            dim: 2,
+            colliders: [
+                {
+                    shape: {
+                        type: "Polyline",
+                        vertices: [
+                            [0, 0],
+                            [1, 0],
+                        ],
+                        indices: [[0, 2]],
+                    },
+                },
+            ],
+        };
+        expect(() => World.importScene(JSON.stringify(polyline))).toThrow(
+            /out of bounds/,
+        );
+
+        const scene = JSON.parse(world.exportScene());
+        scene.dim = 3;
+        expect(() => World.importScene(JSON.stringify(scene))).toThrow();
+    });
+});
@@ -0,0 +1,243 @@
+import {
+    init,
+    ColliderDesc,
+    DeltaSnapshotBase,
+    JointData,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../builds/3d-deterministic/pkg";
+
+describe("3d/Serialization", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+
+        const ground = world.createRigidBody(RigidBodyDesc.fixed());
+        world.createCollider(ColliderDesc.cuboid(10, 0.1, 10), ground);
+
+        for (let i = 0; i < 4; i++) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(0, 0.6 + i * 1.1, 0),
+            );
+            world.createCollider(ColliderDesc.cuboid(0.5, 0.5, 0.5), body);
+        }
+
+        const pendulumAnchor = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(5, 5, 0),
+        );
+        const pendulum = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(6, 5, 0),
+        );
+        world.createCollider(ColliderDesc.ball(0.2), pendulum);
+        world.createImpulseJoint(
+            JointData.spherical(new Vector3(0, 0, 0), new Vector3(-1, 0, 0)),
+            pendulumAnchor,
+            pendulum,
+            true,
+        );
+
+        for (let i = 0; i < 10; i++) {
+            world.step();
+        }
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function stepN(w: World, n: number) {
+        for (let i = 0; i < n; i++) {
+            w.step();
+        }
+    }
+
+    function bodyStates(w: World) {
+        return w.bodies
+            .getAll()
+            .sort((a, b) => a.handle - b.handle)
+            .map((body) => [
+                body.handle,
+                body.translation(),
+                body.rotation(),
+                body.linvel(),
+                body.angvel(),
+            ]);
+    }
+
+    test("snapshot round-trip", () => {
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+
+        stepN(world, 20);
+        stepN(restored, 20);
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+        restored.free();
+    });
+
+    test("snapshot rejects invalid data", () => {
+        const snapshot = world.takeSnapshot();
+        expect(() => World.restoreSnapshot(snapshot.slice(0, 8))).toThrow();
+        expect(() => World.restoreSnapshot(new Uint8Array(64))).toThrow();
+    });
+
+    test("prefab", () => {
+        const handles: number[] = [];
+        world.forEachRigidBody((body) => {
+            if (body.isDynamic()) {
+                handles.push(body.handle);
+            }
+        });
+        const numBodies = world.bodies.len();
+        const prefab = world.serializePrefab(handles);
+        const map = world.insertPrefab(prefab);
+
+        expect(world.bodies.len()).toBe(numBodies + handles.length);
+        expect(map.bodies.size).toBe(handles.length);
+        expect(map.colliders.size).toBe(handles.length);
+        handles.forEach((handle) => {
+            expect(world.getRigidBody(map.bodies.get(handle))).toBeTruthy();
+        });
+    });
+
+    test("delta snapshot round-trip", () => {
+        const base = new DeltaSnapshotBase(world.takeSnapshot());
+        stepN(world, 5);
+
+        const delta = world.takeDeltaSnapshot(base);
+        const restored = World.restoreDeltaSnapshot(base, delta);
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+        expect(restored.bodies.len()).toBe(world.bodies.len());
+        expect(restored.colliders.len()).toBe(world.colliders.len());
+        expect(restored.impulseJoints.len()).toBe(world.impulseJoints.len());
+
+        // The contacts are restored too, so both worlds keep simulating
+        // identically.
+        stepN(world, 20);
+        stepN(restored, 20);
+        expect(bodyStates(restored)).toEqual(bodyStates(world));
+
+        restored.free();
+        base.free();
+    });
+
+    test("delta snapshot rejects a different base", () => {
+        const base = new DeltaSnapshotBase(world.takeSnapshot());
+        const delta = world.takeDeltaSnapshot(base);
+        stepN(world, 5);
+        const otherBase = new DeltaSnapshotBase(world.takeSnapshot());
+
+        expect(() => World.restoreDeltaSnapshot(otherBase, delta)).toThrow();
+
+        otherBase.free();
+        base.free();
+    });
+
+    test("checkpoint round-trip", () => {
+        const saved = bodyStates(world);
+        const checkpoint = world.saveCheckpoint();
+        stepN(world, 20);
+        const stepped = bodyStates(world);
+        expect(stepped).not.toEqual(saved);
+
+        expect(world.restoreCheckpoint(checkpoint)).toBe(true);
+        expect(bodyStates(world)).toEqual(saved);
+
+        stepN(world, 20);
+        expect(bodyStates(world)).toEqual(stepped);
+        checkpoint.free();
+    });
+
+    test("checkpoint restores removed objects", () => {
+        const checkpoint = world.saveCheckpoint();
+        const saved = bodyStates(world);
+        const numBodies = world.bodies.len();
+        const numColliders = world.colliders.len();
+
+        world.forEachRigidBody((body) => {
+            if (body.isDynamic()) {
+                world.removeRigidBody(body);
+            }
+        });
+        expect(world.bodies.len()).toBeLessThan(numBodies);
+
+        expect(world.restoreCheckpoint(checkpoint)).toBe(true);
+        expect(world.bodies.len()).toBe(numBodies);
+        expect(world.colliders.len()).toBe(numColliders);
+        expect(bodyStates(world)).toEqual(saved);
+        checkpoint.free();
+    });
+
+    test("state hash", () => {
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const hash = world.computeStateHash(true);
+        expect(hash.hash).toMatch(/^[0-9a-f]{16}$/);
+        expect(restored.computeStateHash(true).hash).toBe(hash.hash);
+        expect(restored.computeStateHash(true).divergedBodies(hash)).toEqual(
+            [],
+        );
+
+        const body = restored.bodies.getAll().find((b) => b.isDynamic());
+        body.setTranslation(new Vector3(0, 10, 0), true);
+        const moved = restored.computeStateHash(true);
+        expect(moved.hash).not.toBe(hash.hash);
+        expect(moved.divergedBodies(hash)).toEqual([body.handle]);
+        restored.free();
+    });
+
+    test("scene round-trip", () => {
+        const scene = world.exportScene();
+        const imported = World.importScene(scene);
+
+        expect(imported.bodies.len()).toBe(world.bodies.len());
+        expect(imported.colliders.len()).toBe(world.colliders.len());
+        expect(imported.impulseJoints.len()).toBe(world.impulseJoints.len());
+        expect(imported.exportScene()).toBe(scene);
+        imported.free();
+    });
+
+    test("scene import rejects invalid scenes", () => {
+        const scene = JSON.parse(world.exportScene());
+        scene.bodies[0].rotation = [0, 0, 0, 0];
+        expect(() => World.importScene(JSON.stringify(scene))).toThrow(
+            /zero quaternions/,
+        );
+
+        const trimesh = {
+            version: 1,
+            dim: 3,
+            colliders: [
+                {
+                    shape: {
+                        type: "TriMesh",
+                        vertices: [
+                            [0, 0, 0],
+                            [1, 0, 0],
+                            [0, 1, 0],
+                        ],
+                        indices: [[0, 1, 3]],
+                    },
+                },
+            ],
+        };
+        expect(() => World.importScene(JSON.stringify(trimesh))).toThrow(
+            /out of bounds/,
+        );
+
+        const halfSpace = {
+            version: 1,
+            dim: 3,
+            colliders: [{shape: {type: "HalfSpace", normal: [0, 0, 0]}}],
+        };
+        expect(() => World.importScene(JSON.stringify(halfSpace))).toThrow(
+            /normals must not be zero/,
+        );
+    });
+});
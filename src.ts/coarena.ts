@@ -37,6 +37,13 @@ export class Coarena<T> {
         this.data = new Array<T>();
     }
 
+    public clone(): Coarena<T> {
+        let result = new Coarena<T>();
+        result.data = this.data.slice();
+        result.size = this.size;
+        return result;
+    }
+
     public get(handle: number): T | null {
         let i = this.index(handle);
         if (i < this.data.length) {
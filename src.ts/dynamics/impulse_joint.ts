@@ -56,18 +56,40 @@ export enum MotorModel {
  *
  * - `X`: X translation axis
  * - `Y`: Y translation axis
- * - `Z`: Z translation axis
- * - `AngX`: X angular rotation axis
- * - `AngY`: Y angular rotations axis
- * - `AngZ`: Z angular rotation axis
+ * - `Z`: Z translation axis (3D only)
+ * - `AngX`: X angular rotation axis (the only rotation axis in 2D)
+ * - `AngY`: Y angular rotations axis (3D only)
+ * - `AngZ`: Z angular rotation axis (3D only)
  */
 export enum JointAxesMask {
     LinX = 1 << 0,
     LinY = 1 << 1,
+    // #if DIM2
+    AngX = 1 << 2,
+    // #endif
+    // #if DIM3
     LinZ = 1 << 2,
     AngX = 1 << 3,
     AngY = 1 << 4,
     AngZ = 1 << 5,
+    // #endif
+}
+
+/**
+ * An enum representing the individual axes of a joint, expressed in the
+ * joint’s local frame.
+ */
+export enum JointAxis {
+    LinX,
+    LinY,
+    // #if DIM3
+    LinZ,
+    // #endif
+    AngX,
+    // #if DIM3
+    AngY,
+    AngZ,
+    // #endif
 }
 
 export class ImpulseJoint {
@@ -210,6 +232,111 @@ export class ImpulseJoint {
         rawPoint.free();
     }
 
+    /**
+     * Is this joint enabled?
+     *
+     * A disabled joint doesn’t apply any force to the rigid-bodies it is
+     * attached to. Breakable joints are disabled when they break.
+     */
+    public isEnabled(): boolean {
+        return this.rawSet.jointIsEnabled(this.handle);
+    }
+
+    /**
+     * Enables or disables this joint.
+     */
+    public setEnabled(enabled: boolean) {
+        this.rawSet.jointSetEnabled(this.handle, enabled);
+    }
+
+    /**
+     * The total impulse applied by this joint along each of its axes during
+     * the last substep of the last step, including the impulses of its limits
+     * and motors.
+     *
+     * Each step is split into `IntegrationParameters.numSolverIterations`
+     * substeps, and only the impulses of the last one are kept. The force
+     * applied by the joint is approximately this impulse divided by the
+     * substep length, i.e., `dt / numSolverIterations`.
+     *
+     * The impulses are expressed in the local frame of the joint attached to
+     * the first rigid-body:
+     * - 2D: `[linear.x, linear.y, angular]`.
+     * - 3D: `[linear.x, linear.y, linear.z, angular.x, angular.y, angular.z]`.
+     */
+    public impulses(): Float32Array {
+        return this.rawSet.jointImpulses(this.handle);
+    }
+
+    /**
+     * The linear impulse applied by this joint during the last substep,
+     * expressed in the local frame of the joint attached to the first
+     * rigid-body.
+     */
+    public linearImpulse(): Vector {
+        const impulses = this.impulses();
+        // #if DIM2
+        return VectorOps.new(impulses[0], impulses[1]);
+        // #endif
+        // #if DIM3
+        return VectorOps.new(impulses[0], impulses[1], impulses[2]);
+        // #endif
+    }
+
+    // #if DIM2
+    /**
+     * The angular impulse applied by this joint during the last substep.
+     */
+    public angularImpulse(): number {
+        return this.impulses()[2];
+    }
+    // #endif
+
+    // #if DIM3
+    /**
+     * The angular impulse applied by this joint during the last substep,
+     * expressed in the local frame of the joint attached to the first
+     * rigid-body.
+     */
+    public angularImpulse(): Vector {
+        const impulses = this.impulses();
+        return VectorOps.new(impulses[3], impulses[4], impulses[5]);
+    }
+    // #endif
+
+    /**
+     * Makes this joint breakable.
+     *
+     * At the end of each step, this joint is disabled and a joint break event
+     * is generated (see `EventQueue.drainJointBreakEvents`) if the magnitude
+     * of the linear force it applied exceeds `maxForce`, or if the magnitude
+     * of the torque it applied exceeds `maxTorque`. These are computed from
+     * the impulses of the last substep divided by the substep length (see
+     * `ImpulseJoint.impulses`).
+     *
+     * @param maxForce - The maximum linear force this joint can apply.
+     * @param maxTorque - The maximum torque this joint can apply.
+     */
+    public setBreakThresholds(maxForce: number, maxTorque: number) {
+        this.rawSet.jointSetBreakThresholds(this.handle, maxForce, maxTorque);
+    }
+
+    /**
+     * The magnitude of the linear force above which this joint breaks, or
+     * `Infinity` if it isn’t breakable.
+     */
+    public breakForce(): number {
+        return this.rawSet.jointBreakForce(this.handle);
+    }
+
+    /**
+     * The magnitude of the torque above which this joint breaks, or
+     * `Infinity` if it isn’t breakable.
+     */
+    public breakTorque(): number {
+        return this.rawSet.jointBreakTorque(this.handle);
+    }
+
     /**
      * Controls whether contacts are computed between colliders attached
      * to the rigid-bodies linked by this joint.
@@ -339,7 +466,56 @@ export class RevoluteImpulseJoint extends UnitImpulseJoint {
 }
 
 // #if DIM3
-export class GenericImpulseJoint extends ImpulseJoint {}
+export class GenericImpulseJoint extends ImpulseJoint {
+    /**
+     * Sets the limits of this joint along the given axis.
+     *
+     * @param axis - The axis to limit.
+     * @param min - The minimum bound of the joint coordinate along this axis.
+     * @param max - The maximum bound of the joint coordinate along this axis.
+     */
+    public setLimits(axis: JointAxis, min: number, max: number) {
+        this.rawSet.jointSetLimits(
+            this.handle,
+            axis as number as RawJointAxis,
+            min,
+            max,
+        );
+    }
+
+    public configureMotorModel(axis: JointAxis, model: MotorModel) {
+        this.rawSet.jointConfigureMotorModel(
+            this.handle,
+            axis as number as RawJointAxis,
+            model as number as RawMotorModel,
+        );
+    }
+
+    public setMotorMaxForce(axis: JointAxis, maxForce: number) {
+        this.rawSet.jointSetMotorMaxForce(
+            this.handle,
+            axis as number as RawJointAxis,
+            maxForce,
+        );
+    }
+
+    public configureMotor(
+        axis: JointAxis,
+        targetPos: number,
+        targetVel: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotor(
+            this.handle,
+            axis as number as RawJointAxis,
+            targetPos,
+            targetVel,
+            stiffness,
+            damping,
+        );
+    }
+}
 
 export class SphericalImpulseJoint extends ImpulseJoint {
     /* Unsupported by this alpha release.
@@ -365,6 +541,15 @@ export class SphericalImpulseJoint extends ImpulseJoint {
 }
 // #endif
 
+/**
+ * The description of a joint, used to create an impulse joint or a multibody
+ * joint.
+ *
+ * There is no per-joint softness: the softness of all the joint constraints
+ * is controlled globally by `IntegrationParameters.jointNaturalFrequency` and
+ * `IntegrationParameters.jointDampingRatio`. Only the motors can be given their
+ * own stiffness and damping.
+ */
 export class JointData {
     anchor1: Vector;
     anchor2: Vector;
@@ -382,9 +567,88 @@ export class JointData {
     stiffness: number;
     damping: number;
     length: number;
+    coupledAxes: JointAxesMask;
+    private axisConfigs: Array<(raw: RawGenericJoint) => void> = [];
 
     private constructor() {}
 
+    /**
+     * Sets the limits of the joint to be created along the given axis.
+     *
+     * @param axis - The axis to limit.
+     * @param min - The minimum bound of the joint coordinate along this axis.
+     * @param max - The maximum bound of the joint coordinate along this axis.
+     */
+    public setLimits(axis: JointAxis, min: number, max: number): JointData {
+        const rawAxis = axis as number as RawJointAxis;
+        this.axisConfigs.push((raw) => raw.setLimits(rawAxis, min, max));
+        return this;
+    }
+
+    /**
+     * Configures the motor of the joint to be created along the given axis.
+     *
+     * The motor drives the joint coordinate along this axis towards
+     * `targetPos` with the given `stiffness`, and its velocity towards
+     * `targetVel` with the given `damping`. With a zero `targetVel`, this
+     * acts as a spring-damper with rest position `targetPos`.
+     *
+     * @param axis - The axis to motorize.
+     * @param targetPos - The target joint coordinate along this axis.
+     * @param targetVel - The target joint velocity along this axis.
+     * @param stiffness - The stiffness of the motor.
+     * @param damping - The damping of the motor.
+     */
+    public setMotor(
+        axis: JointAxis,
+        targetPos: number,
+        targetVel: number,
+        stiffness: number,
+        damping: number,
+    ): JointData {
+        const rawAxis = axis as number as RawJointAxis;
+        this.axisConfigs.push((raw) =>
+            raw.setMotor(rawAxis, targetPos, targetVel, stiffness, damping),
+        );
+        return this;
+    }
+
+    /**
+     * Sets the model used by the motor of the joint to be created along the
+     * given axis.
+     */
+    public setMotorModel(axis: JointAxis, model: MotorModel): JointData {
+        const rawAxis = axis as number as RawJointAxis;
+        const rawModel = model as number as RawMotorModel;
+        this.axisConfigs.push((raw) => raw.setMotorModel(rawAxis, rawModel));
+        return this;
+    }
+
+    /**
+     * Sets the maximum force the motor of the joint to be created along the
+     * given axis can deliver.
+     */
+    public setMotorMaxForce(axis: JointAxis, maxForce: number): JointData {
+        const rawAxis = axis as number as RawJointAxis;
+        this.axisConfigs.push((raw) => raw.setMotorMaxForce(rawAxis, maxForce));
+        return this;
+    }
+
+    /**
+     * Sets the coupled axes of the joint to be created.
+     *
+     * Only the limits and motor of the first coupled linear (resp. angular)
+     * axis are applied, to all the coupled linear (resp. angular) axes at
+     * once.
+     *
+     * @param coupledAxes - Mask of the coupled axes. In 2D, only `LinX`,
+     *   `LinY`, and `AngX` are available.
+     */
+    public setCoupledAxes(coupledAxes: JointAxesMask): JointData {
+        this.coupledAxes = coupledAxes;
+        return this;
+    }
+
     /**
      * Creates a new joint descriptor that builds a Fixed joint.
      *
@@ -703,6 +967,7 @@ export class JointData {
                     rawAx,
                     rawAxesMask,
                 );
+                rawAx.free();
                 break;
             case JointType.Spherical:
                 result = RawGenericJoint.spherical(rawA1, rawA2);
@@ -731,6 +996,13 @@ export class JointData {
         rawA1.free();
         rawA2.free();
 
+        if (!!result) {
+            if (this.coupledAxes !== undefined) {
+                result.setCoupledAxes(this.coupledAxes);
+            }
+            this.axisConfigs.forEach((config) => config(result));
+        }
+
         return result;
     }
 }
@@ -58,6 +58,21 @@ export class ImpulseJointSet {
         this.map.forEach((joint) => joint.finalizeDeserialization(bodies));
     }
 
+    /** @internal */
+    public mapExisting(handle: ImpulseJointHandle, bodies: RigidBodySet) {
+        this.map.set(handle, ImpulseJoint.newTyped(this.raw, bodies, handle));
+    }
+
+    /** @internal */
+    public saveMap(): Coarena<ImpulseJoint> {
+        return this.map.clone();
+    }
+
+    /** @internal */
+    public restoreMap(map: Coarena<ImpulseJoint>) {
+        this.map = map.clone();
+    }
+
     /**
      * Creates a new joint and return its integer handle.
      *
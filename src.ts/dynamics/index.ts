@@ -1,5 +1,6 @@
 export * from "./rigid_body";
 export * from "./rigid_body_set";
+export * from "./rigid_body_poses";
 export * from "./integration_parameters";
 export * from "./impulse_joint";
 export * from "./impulse_joint_set";
@@ -82,6 +82,28 @@ export class IntegrationParameters {
         return this.raw.maxCcdSubsteps;
     }
 
+    /**
+     * The natural frequency used by the springs regularizing the joint
+     * constraints (default: `1.0e6`).
+     *
+     * Lower values make all the joints softer. This applies to every joint of
+     * the world: the softness can’t be configured per joint.
+     */
+    get jointNaturalFrequency(): number {
+        return this.raw.jointNaturalFrequency;
+    }
+
+    /**
+     * The fraction of critical damping applied by the springs regularizing
+     * the joint constraints (default: `1.0`).
+     *
+     * Larger values make the joints more compliant. This applies to every
+     * joint of the world: the damping can’t be configured per joint.
+     */
+    get jointDampingRatio(): number {
+        return this.raw.jointDampingRatio;
+    }
+
     set dt(value: number) {
         this.raw.dt = value;
     }
@@ -123,4 +145,12 @@ export class IntegrationParameters {
     set maxCcdSubsteps(value: number) {
         this.raw.maxCcdSubsteps = value;
     }
+
+    set jointNaturalFrequency(value: number) {
+        this.raw.jointNaturalFrequency = value;
+    }
+
+    set jointDampingRatio(value: number) {
+        this.raw.jointDampingRatio = value;
+    }
 }
@@ -1,5 +1,72 @@
-import {RawIslandManager} from "../raw";
+import {RawIslandManager, RawIslands} from "../raw";
 import {RigidBodyHandle} from "./rigid_body";
+import {RigidBodySet} from "./rigid_body_set";
+import {ImpulseJointSet} from "./impulse_joint_set";
+import {MultibodyJointSet} from "./multibody_joint_set";
+import {ColliderSet, NarrowPhase} from "../geometry";
+
+/**
+ * The islands of a physics state, computed by `IslandManager.islands`.
+ *
+ * An island is a set of dynamic rigid-bodies connected, directly or indirectly, by
+ * active contacts or enabled joints. Islands are numbered from `0` to
+ * `numIslands() - 1`.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `islands.free()`
+ * once you are done using it.
+ */
+export class Islands {
+    raw: RawIslands;
+
+    /**
+     * Release the WASM memory occupied by these islands.
+     */
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw: RawIslands) {
+        this.raw = raw;
+    }
+
+    /**
+     * The number of islands.
+     */
+    public numIslands(): number {
+        return this.raw.numIslands();
+    }
+
+    /**
+     * The handles of the rigid-bodies of the given island.
+     *
+     * @param island - The index of the island.
+     */
+    public islandBodies(island: number): Float64Array {
+        return this.raw.islandBodies(island);
+    }
+
+    /**
+     * Are all the rigid-bodies of the given island sleeping?
+     *
+     * @param island - The index of the island.
+     */
+    public isIslandSleeping(island: number): boolean {
+        return this.raw.isIslandSleeping(island);
+    }
+
+    /**
+     * The index of the island the given rigid-body belongs to, or `undefined` if
+     * it isn’t a dynamic rigid-body.
+     *
+     * @param handle - The handle of the rigid-body.
+     */
+    public bodyIsland(handle: RigidBodyHandle): number | undefined {
+        return this.raw.bodyIsland(handle);
+    }
+}
 
 /**
  * The CCD solver responsible for resolving Continuous Collision Detection.
@@ -34,4 +101,32 @@ export class IslandManager {
     public forEachActiveRigidBodyHandle(f: (handle: RigidBodyHandle) => void) {
         this.raw.forEachActiveRigidBodyHandle(f);
     }
+
+    /**
+     * Computes the islands of the given physics state.
+     *
+     * Interactions with fixed or kinematic rigid-bodies don’t connect islands, and
+     * sleeping rigid-bodies are included.
+     *
+     * These islands are reconstructed from the contact and joint graphs of the
+     * given sets at each call. They are not the islands used internally by the
+     * physics pipeline for solving and sleeping, which are not exposed.
+     */
+    public islands(
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        narrowPhase: NarrowPhase,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): Islands {
+        return new Islands(
+            this.raw.islands(
+                bodies.raw,
+                colliders.raw,
+                narrowPhase.raw,
+                impulseJoints.raw,
+                multibodyJoints.raw,
+            ),
+        );
+    }
 }
@@ -1,17 +1,22 @@
+import {Rotation, Vector, VectorOps, RotationOps} from "../math";
 import {
     RawImpulseJointSet,
     RawJointAxis,
     RawJointType,
+    RawMotorModel,
     RawMultibodyJointSet,
 } from "../raw";
 import {
     FixedImpulseJoint,
     ImpulseJointHandle,
+    JointAxesMask,
     JointType,
     MotorModel,
     PrismaticImpulseJoint,
     RevoluteImpulseJoint,
 } from "./impulse_joint";
+import {RigidBodyHandle} from "./rigid_body";
+import {RigidBodySet} from "./rigid_body_set";
 
 // #if DIM3
 import {Quaternion} from "../math";
@@ -25,33 +30,45 @@ export type MultibodyJointHandle = number;
 
 export class MultibodyJoint {
     protected rawSet: RawMultibodyJointSet; // The MultibodyJoint won't need to free this.
+    protected bodySet: RigidBodySet; // The MultibodyJoint won’t need to free this.
     handle: MultibodyJointHandle;
 
-    constructor(rawSet: RawMultibodyJointSet, handle: MultibodyJointHandle) {
+    constructor(
+        rawSet: RawMultibodyJointSet,
+        bodySet: RigidBodySet,
+        handle: MultibodyJointHandle,
+    ) {
         this.rawSet = rawSet;
+        this.bodySet = bodySet;
         this.handle = handle;
     }
 
     public static newTyped(
         rawSet: RawMultibodyJointSet,
+        bodySet: RigidBodySet,
         handle: MultibodyJointHandle,
     ): MultibodyJoint {
         switch (rawSet.jointType(handle)) {
             case RawJointType.Revolute:
-                return new RevoluteMultibodyJoint(rawSet, handle);
+                return new RevoluteMultibodyJoint(rawSet, bodySet, handle);
             case RawJointType.Prismatic:
-                return new PrismaticMultibodyJoint(rawSet, handle);
+                return new PrismaticMultibodyJoint(rawSet, bodySet, handle);
             case RawJointType.Fixed:
-                return new FixedMultibodyJoint(rawSet, handle);
+                return new FixedMultibodyJoint(rawSet, bodySet, handle);
             // #if DIM3
             case RawJointType.Spherical:
-                return new SphericalMultibodyJoint(rawSet, handle);
+                return new SphericalMultibodyJoint(rawSet, bodySet, handle);
             // #endif
             default:
-                return new MultibodyJoint(rawSet, handle);
+                return new MultibodyJoint(rawSet, bodySet, handle);
         }
     }
 
+    /** @internal */
+    public finalizeDeserialization(bodySet: RigidBodySet) {
+        this.bodySet = bodySet;
+    }
+
     /**
      * Checks if this joint is still valid (i.e. that it has
      * not been deleted from the joint set yet).
@@ -60,66 +77,150 @@ export class MultibodyJoint {
         return this.rawSet.contains(this.handle);
     }
 
-    // /**
-    //  * The unique integer identifier of the first rigid-body this joint it attached to.
-    //  */
-    // public bodyHandle1(): RigidBodyHandle {
-    //     return this.rawSet.jointBodyHandle1(this.handle);
-    // }
-    //
-    // /**
-    //  * The unique integer identifier of the second rigid-body this joint is attached to.
-    //  */
-    // public bodyHandle2(): RigidBodyHandle {
-    //     return this.rawSet.jointBodyHandle2(this.handle);
-    // }
-    //
-    // /**
-    //  * The type of this joint given as a string.
-    //  */
-    // public type(): JointType {
-    //     return this.rawSet.jointType(this.handle);
-    // }
-    //
-    // // #if DIM3
-    // /**
-    //  * The rotation quaternion that aligns this joint's first local axis to the `x` axis.
-    //  */
-    // public frameX1(): Rotation {
-    //     return RotationOps.fromRaw(this.rawSet.jointFrameX1(this.handle));
-    // }
-    //
-    // // #endif
-    //
-    // // #if DIM3
-    // /**
-    //  * The rotation matrix that aligns this joint's second local axis to the `x` axis.
-    //  */
-    // public frameX2(): Rotation {
-    //     return RotationOps.fromRaw(this.rawSet.jointFrameX2(this.handle));
-    // }
-    //
-    // // #endif
-    //
-    // /**
-    //  * The position of the first anchor of this joint.
-    //  *
-    //  * The first anchor gives the position of the points application point on the
-    //  * local frame of the first rigid-body it is attached to.
-    //  */
-    // public anchor1(): Vector {
-    //     return VectorOps.fromRaw(this.rawSet.jointAnchor1(this.handle));
-    // }
-    //
-    // /**
-    //  * The position of the second anchor of this joint.
-    //  *
-    //  * The second anchor gives the position of the points application point on the
-    //  * local frame of the second rigid-body it is attached to.
-    //  */
-    // public anchor2(): Vector {
-    //     return VectorOps.fromRaw(this.rawSet.jointAnchor2(this.handle));
-    // }
+    /**
+     * The handle of the parent rigid-body of the multibody link this joint
+     * is attached to.
+     *
+     * Returns `undefined` if this joint is attached to the root of its
+     * multibody.
+     */
+    public bodyHandle1(): RigidBodyHandle | undefined {
+        return this.rawSet.jointBodyHandle1(this.handle);
+    }
+
+    /**
+     * The handle of the rigid-body of the multibody link this joint is
+     * attached to.
+     */
+    public bodyHandle2(): RigidBodyHandle {
+        return this.rawSet.jointBodyHandle2(this.handle);
+    }
+
+    /**
+     * The type of this joint given as a string.
+     */
+    public type(): JointType {
+        return this.rawSet.jointType(this.handle) as number as JointType;
+    }
+
+    // #if DIM3
+    /**
+     * The rotation quaternion that aligns this joint's first local axis to the `x` axis.
+     */
+    public frameX1(): Rotation {
+        return RotationOps.fromRaw(this.rawSet.jointFrameX1(this.handle));
+    }
+
+    // #endif
+
+    // #if DIM3
+    /**
+     * The rotation matrix that aligns this joint's second local axis to the `x` axis.
+     */
+    public frameX2(): Rotation {
+        return RotationOps.fromRaw(this.rawSet.jointFrameX2(this.handle));
+    }
+
+    // #endif
+
+    /**
+     * The position of the first anchor of this joint.
+     *
+     * The first anchor gives the position of the application point on the
+     * local frame of the first rigid-body it is attached to.
+     */
+    public anchor1(): Vector {
+        return VectorOps.fromRaw(this.rawSet.jointAnchor1(this.handle));
+    }
+
+    /**
+     * The position of the second anchor of this joint.
+     *
+     * The second anchor gives the position of the application point on the
+     * local frame of the second rigid-body it is attached to.
+     */
+    public anchor2(): Vector {
+        return VectorOps.fromRaw(this.rawSet.jointAnchor2(this.handle));
+    }
+
+    /**
+     * The number of degrees of freedom of this joint.
+     */
+    public ndofs(): number {
+        return this.rawSet.jointNdofs(this.handle);
+    }
+
+    /**
+     * The coordinates of this joint along each of its free axes.
+     *
+     * This is the position of the link this joint is attached to, relative
+     * to its parent: the translations along the free linear axes first,
+     * followed by the rotations (in radians) along the free angular axes.
+     */
+    public coords(): Float32Array {
+        return this.rawSet.jointCoords(this.handle);
+    }
+
+    /**
+     * The velocities of this joint along each of its free axes, in the same
+     * order as `coords`.
+     */
+    public velocities(): Float32Array {
+        return this.rawSet.jointVelocities(this.handle);
+    }
+
+    /**
+     * The number of degrees of freedom of the multibody this joint is part
+     * of.
+     */
+    public multibodyNdofs(): number {
+        return this.rawSet.multibodyNdofs(this.handle);
+    }
+
+    /**
+     * The handles of the rigid-bodies of each link of the multibody this
+     * joint is part of, starting with the root link.
+     *
+     * This is also the order of the links’ degrees of freedom in the
+     * generalized coordinates, velocities, jacobians, and mass matrix of the
+     * multibody.
+     */
+    public multibodyLinkBodies(): Float64Array {
+        return this.rawSet.multibodyLinkBodies(this.handle);
+    }
+
+    /**
+     * The generalized coordinates of the multibody this joint is part of.
+     *
+     * This is the concatenation of the `coords` of the joint of each link, in
+     * the order given by `multibodyLinkBodies`.
+     */
+    public generalizedCoords(): Float32Array {
+        return this.rawSet.multibodyGeneralizedCoords(this.handle);
+    }
+
+    /**
+     * The generalized velocities of the multibody this joint is part of.
+     */
+    public generalizedVelocities(): Float32Array {
+        return this.rawSet.multibodyGeneralizedVelocities(this.handle);
+    }
+
+    /**
+     * The jacobian of the multibody link this joint is attached to.
+     *
+     * The jacobian maps the generalized velocities of the multibody to the
+     * world-space linear velocity (at the center of mass) and angular
+     * velocity of the link’s rigid-body. It is a column-major matrix with one
+     * column per degree of freedom of the multibody, and the following rows:
+     * - 2D: `[linvel.x, linvel.y, angvel]`.
+     * - 3D: `[linvel.x, linvel.y, linvel.z, angvel.x, angvel.y, angvel.z]`.
+     *
+     * The jacobians are updated at each timestep.
+     */
+    public jacobian(): Float32Array {
+        return this.rawSet.multibodyLinkJacobian(this.handle);
+    }
 
     /**
      * Controls whether contacts are computed between colliders attached
@@ -144,46 +245,152 @@ export class UnitMultibodyJoint extends MultibodyJoint {
      */
     protected rawAxis?(): RawJointAxis;
 
-    // /**
-    //  * Are the limits enabled for this joint?
-    //  */
-    // public limitsEnabled(): boolean {
-    //     return this.rawSet.jointLimitsEnabled(this.handle, this.rawAxis());
-    // }
-    //
-    // /**
-    //  * The min limit of this joint.
-    //  */
-    // public limitsMin(): number {
-    //     return this.rawSet.jointLimitsMin(this.handle, this.rawAxis());
-    // }
-    //
-    // /**
-    //  * The max limit of this joint.
-    //  */
-    // public limitsMax(): number {
-    //     return this.rawSet.jointLimitsMax(this.handle, this.rawAxis());
-    // }
-    //
-    // public configureMotorModel(model: MotorModel) {
-    //     this.rawSet.jointConfigureMotorModel(this.handle, this.rawAxis(), model);
-    // }
-    //
-    // public configureMotorVelocity(targetVel: number, factor: number) {
-    //     this.rawSet.jointConfigureMotorVelocity(this.handle, this.rawAxis(), targetVel, factor);
-    // }
-    //
-    // public configureMotorPosition(targetPos: number, stiffness: number, damping: number) {
-    //     this.rawSet.jointConfigureMotorPosition(this.handle, this.rawAxis(), targetPos, stiffness, damping);
-    // }
-    //
-    // public configureMotor(targetPos: number, targetVel: number, stiffness: number, damping: number) {
-    //     this.rawSet.jointConfigureMotor(this.handle, this.rawAxis(), targetPos, targetVel, stiffness, damping);
-    // }
+    /**
+     * Are the limits enabled for this joint?
+     */
+    public limitsEnabled(): boolean {
+        return this.rawSet.jointLimitsEnabled(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The min limit of this joint.
+     */
+    public limitsMin(): number {
+        return this.rawSet.jointLimitsMin(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The max limit of this joint.
+     */
+    public limitsMax(): number {
+        return this.rawSet.jointLimitsMax(this.handle, this.rawAxis());
+    }
+
+    /**
+     * Sets the limits of this joint.
+     *
+     * This wakes up the rigid-bodies of the multibody, like all the limit and
+     * motor setters.
+     *
+     * @param min - The minimum bound of this joint’s free coordinate.
+     * @param max - The maximum bound of this joint’s free coordinate.
+     */
+    public setLimits(min: number, max: number) {
+        this.rawSet.jointSetLimits(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+            min,
+            max,
+        );
+    }
+
+    public configureMotorModel(model: MotorModel) {
+        this.rawSet.jointConfigureMotorModel(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+            model as number as RawMotorModel,
+        );
+    }
+
+    public setMotorMaxForce(maxForce: number) {
+        this.rawSet.jointSetMotorMaxForce(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+            maxForce,
+        );
+    }
+
+    public configureMotorVelocity(targetVel: number, factor: number) {
+        this.rawSet.jointConfigureMotorVelocity(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+            targetVel,
+            factor,
+        );
+    }
+
+    public configureMotorPosition(
+        targetPos: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotorPosition(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+            targetPos,
+            stiffness,
+            damping,
+        );
+    }
+
+    public configureMotor(
+        targetPos: number,
+        targetVel: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotor(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+            targetPos,
+            targetVel,
+            stiffness,
+            damping,
+        );
+    }
 }
 
 export class FixedMultibodyJoint extends MultibodyJoint {}
 
+/**
+ * Options of the jacobian-based inverse-kinematics solver for multibodies.
+ */
+export class InverseKinematicsOptions {
+    /**
+     * The damping coefficient of the solver.
+     *
+     * Small values can lead to overshooting preventing convergence. Large
+     * values can slow down convergence, requiring more iterations.
+     */
+    damping: number = 1.0;
+    /**
+     * The maximum number of iterations of the solver.
+     */
+    maxIters: number = 10;
+    /**
+     * The axes of the link’s pose the solver will solve for.
+     */
+    // #if DIM2
+    constrainedAxes: JointAxesMask =
+        JointAxesMask.LinX | JointAxesMask.LinY | JointAxesMask.AngX;
+    // #endif
+    // #if DIM3
+    constrainedAxes: JointAxesMask =
+        JointAxesMask.LinX |
+        JointAxesMask.LinY |
+        JointAxesMask.LinZ |
+        JointAxesMask.AngX |
+        JointAxesMask.AngY |
+        JointAxesMask.AngZ;
+    // #endif
+    /**
+     * The solver stops once both the linear error and the angular error fall
+     * below `epsilonLinear` and `epsilonAngular`.
+     */
+    epsilonLinear: number = 1.0e-3;
+    /**
+     * The solver stops once both the linear error and the angular error fall
+     * below `epsilonLinear` and `epsilonAngular`.
+     */
+    epsilonAngular: number = 1.0e-3;
+}
+
 export class PrismaticMultibodyJoint extends UnitMultibodyJoint {
     public rawAxis(): RawJointAxis {
         return RawJointAxis.LinX;
@@ -1,7 +1,9 @@
 import {RawMultibodyJointSet} from "../raw";
 import {Coarena} from "../coarena";
 import {RigidBodySet} from "./rigid_body_set";
+import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {
+    InverseKinematicsOptions,
     MultibodyJoint,
     MultibodyJointHandle,
     RevoluteMultibodyJoint,
@@ -47,20 +49,45 @@ export class MultibodyJointSet {
         // Initialize the map with the existing elements, if any.
         if (raw) {
             raw.forEachJointHandle((handle: MultibodyJointHandle) => {
-                this.map.set(handle, MultibodyJoint.newTyped(this.raw, handle));
+                this.map.set(
+                    handle,
+                    MultibodyJoint.newTyped(this.raw, null, handle),
+                );
             });
         }
     }
 
+    /** @internal */
+    public finalizeDeserialization(bodies: RigidBodySet) {
+        this.map.forEach((joint) => joint.finalizeDeserialization(bodies));
+    }
+
+    /** @internal */
+    public mapExisting(handle: MultibodyJointHandle, bodies: RigidBodySet) {
+        this.map.set(handle, MultibodyJoint.newTyped(this.raw, bodies, handle));
+    }
+
+    /** @internal */
+    public saveMap(): Coarena<MultibodyJoint> {
+        return this.map.clone();
+    }
+
+    /** @internal */
+    public restoreMap(map: Coarena<MultibodyJoint>) {
+        this.map = map.clone();
+    }
+
     /**
      * Creates a new joint and return its integer handle.
      *
+     * @param bodies - The set of rigid-bodies containing the bodies the joint is attached to.
      * @param desc - The joint's parameters.
      * @param parent1 - The handle of the first rigid-body this joint is attached to.
      * @param parent2 - The handle of the second rigid-body this joint is attached to.
      * @param wakeUp - Should the attached rigid-bodies be awakened?
      */
     public createJoint(
+        bodies: RigidBodySet,
         desc: JointData,
         parent1: RigidBodyHandle,
         parent2: RigidBodyHandle,
@@ -74,7 +101,7 @@ export class MultibodyJointSet {
             wakeUp,
         );
         rawParams.free();
-        let joint = MultibodyJoint.newTyped(this.raw, handle);
+        let joint = MultibodyJoint.newTyped(this.raw, bodies, handle);
         this.map.set(handle, joint);
         return joint;
     }
@@ -154,4 +181,99 @@ export class MultibodyJointSet {
     public getAll(): MultibodyJoint[] {
         return this.map.getAll();
     }
+
+    /**
+     * Computes, with inverse kinematics, the joint displacements needed to
+     * move the multibody link a joint is attached to at the given pose.
+     *
+     * Returns the displacements in generalized coordinates, with one value
+     * per degree of freedom of the whole multibody the link is part of.
+     *
+     * @param bodies - The set of rigid-bodies the multibody is made of.
+     * @param handle - The handle of the joint attached to the link to move.
+     * @param targetTranslation - The desired world-space translation of the
+     *   link.
+     * @param targetRotation - The desired world-space rotation of the link.
+     * @param options - The options of the inverse-kinematics solver.
+     * @param apply - If `true`, the displacements are applied to the
+     *   multibody, the poses of its rigid-bodies are updated accordingly, and
+     *   these rigid-bodies are woken up.
+     */
+    public inverseKinematics(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+        targetTranslation: Vector,
+        targetRotation: Rotation,
+        options?: InverseKinematicsOptions,
+        apply: boolean = true,
+    ): Float32Array {
+        options = options ?? new InverseKinematicsOptions();
+        const rawTra = VectorOps.intoRaw(targetTranslation);
+        const rawRot = RotationOps.intoRaw(targetRotation);
+        const result = this.raw.inverseKinematics(
+            bodies.raw,
+            handle,
+            rawTra,
+            rawRot,
+            options.constrainedAxes,
+            options.damping,
+            options.maxIters,
+            options.epsilonLinear,
+            options.epsilonAngular,
+            apply,
+        );
+        rawTra.free();
+        rawRot.free();
+        return result;
+    }
+
+    /**
+     * The mass matrix, in generalized coordinates, of the multibody a joint
+     * is part of.
+     *
+     * This is a symmetric matrix with one row and one column per degree of
+     * freedom of the multibody, packed in column-major order. It doesn’t
+     * include the joint damping.
+     *
+     * @param bodies - The set of rigid-bodies the multibody is made of.
+     * @param handle - The handle of a joint of the multibody.
+     */
+    public massMatrix(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+    ): Float32Array {
+        return this.raw.multibodyMassMatrix(bodies.raw, handle);
+    }
+
+    /**
+     * Applies a generalized impulse to the multibody a joint is part of.
+     *
+     * The generalized velocities of the multibody are increased by the
+     * product of the inverse of its mass matrix by `impulses`. To apply a
+     * generalized force during the next timestep, apply an impulse equal to
+     * the force multiplied by the timestep length.
+     *
+     * Throws an error if the mass matrix of the multibody can’t be inverted,
+     * which is always the case before the first timestep following the
+     * creation of the multibody.
+     *
+     * @param bodies - The set of rigid-bodies the multibody is made of.
+     * @param handle - The handle of a joint of the multibody.
+     * @param impulses - The generalized impulse, with one value per degree
+     *   of freedom of the multibody.
+     * @param wakeUp - Should the rigid-bodies of the multibody be awakened?
+     */
+    public applyGeneralizedImpulse(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+        impulses: Float32Array,
+        wakeUp: boolean,
+    ) {
+        this.raw.multibodyApplyGeneralizedImpulse(
+            bodies.raw,
+            handle,
+            impulses,
+            wakeUp,
+        );
+    }
 }
@@ -771,6 +771,64 @@ export class RigidBody {
         return this.rawSet.rbIsSleeping(this.handle);
     }
 
+    /**
+     * The linear velocity below which this rigid-body can fall asleep, divided by the
+     * `lengthUnit` of the integration parameters (default: `0.4`).
+     *
+     * A negative value means this rigid-body never falls asleep.
+     */
+    public normalizedLinearSleepThreshold(): number {
+        return this.rawSet.rbNormalizedLinearSleepThreshold(this.handle);
+    }
+
+    /**
+     * Sets the linear velocity below which this rigid-body can fall asleep, divided by the
+     * `lengthUnit` of the integration parameters.
+     *
+     * @param threshold - The new threshold. A negative value prevents this rigid-body from
+     *                    falling asleep.
+     */
+    public setNormalizedLinearSleepThreshold(threshold: number) {
+        this.rawSet.rbSetNormalizedLinearSleepThreshold(this.handle, threshold);
+    }
+
+    /**
+     * The angular velocity below which this rigid-body can fall asleep (default: `0.5`).
+     *
+     * A negative value means this rigid-body never falls asleep.
+     */
+    public angularSleepThreshold(): number {
+        return this.rawSet.rbAngularSleepThreshold(this.handle);
+    }
+
+    /**
+     * Sets the angular velocity below which this rigid-body can fall asleep.
+     *
+     * @param threshold - The new threshold. A negative value prevents this rigid-body from
+     *                    falling asleep.
+     */
+    public setAngularSleepThreshold(threshold: number) {
+        this.rawSet.rbSetAngularSleepThreshold(this.handle, threshold);
+    }
+
+    /**
+     * The time, in seconds, this rigid-body must remain below its sleep thresholds before
+     * falling asleep (default: `2.0`).
+     */
+    public timeUntilSleep(): number {
+        return this.rawSet.rbTimeUntilSleep(this.handle);
+    }
+
+    /**
+     * Sets the time, in seconds, this rigid-body must remain below its sleep thresholds before
+     * falling asleep.
+     *
+     * @param time - The new time until sleep.
+     */
+    public setTimeUntilSleep(time: number) {
+        this.rawSet.rbSetTimeUntilSleep(this.handle, time);
+    }
+
     /**
      * Is the velocity of this rigid-body not zero?
      */
@@ -1140,6 +1198,9 @@ export class RigidBodyDesc {
     status: RigidBodyType;
     canSleep: boolean;
     sleeping: boolean;
+    normalizedLinearSleepThreshold: number;
+    angularSleepThreshold: number;
+    timeUntilSleep: number;
     ccdEnabled: boolean;
     softCcdPrediction: number;
     dominanceGroup: number;
@@ -1176,6 +1237,9 @@ export class RigidBodyDesc {
         this.angularDamping = 0.0;
         this.canSleep = true;
         this.sleeping = false;
+        this.normalizedLinearSleepThreshold = 0.4;
+        this.angularSleepThreshold = 0.5;
+        this.timeUntilSleep = 2.0;
         this.ccdEnabled = false;
         this.softCcdPrediction = 0.0;
         this.dominanceGroup = 0;
@@ -1652,6 +1716,37 @@ export class RigidBodyDesc {
         return this;
     }
 
+    /**
+     * Sets the velocities below which the rigid-body to create can fall asleep.
+     *
+     * These thresholds are ignored if the rigid-body can’t sleep (see `setCanSleep`).
+     *
+     * @param normalizedLinearThreshold - The linear velocity threshold, divided by the
+     *   `lengthUnit` of the integration parameters (default: `0.4`). A negative value prevents
+     *   the rigid-body from falling asleep.
+     * @param angularThreshold - The angular velocity threshold (default: `0.5`). A negative
+     *   value prevents the rigid-body from falling asleep.
+     */
+    public setSleepThresholds(
+        normalizedLinearThreshold: number,
+        angularThreshold: number,
+    ): RigidBodyDesc {
+        this.normalizedLinearSleepThreshold = normalizedLinearThreshold;
+        this.angularSleepThreshold = angularThreshold;
+        return this;
+    }
+
+    /**
+     * Sets the time, in seconds, the rigid-body to create must remain below its sleep
+     * thresholds before falling asleep.
+     *
+     * @param time - The time until sleep (default: `2.0`).
+     */
+    public setTimeUntilSleep(time: number): RigidBodyDesc {
+        this.timeUntilSleep = time;
+        return this;
+    }
+
     /**
      * Sets whether Continuous Collision Detection (CCD) is enabled for this rigid-body.
      *
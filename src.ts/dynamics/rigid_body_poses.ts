@@ -0,0 +1,97 @@
+import {RawRigidBodyPoses} from "../raw";
+import {RigidBodyHandle} from "./rigid_body";
+
+/**
+ * A packed buffer of rigid-body poses, filled by `RigidBodySet.exportPoses`.
+ *
+ * Each rigid-body occupies `RigidBodyPoses.stride()` consecutive floats:
+ * - In 2D: `[handle, handle, tx, ty, angle, padding]`.
+ * - In 3D: `[handle, handle, tx, ty, tz, qx, qy, qz, qw, padding]`.
+ *
+ * The two first floats of each entry contain the bits of the rigid-body handle.
+ * Use `RigidBodyPoses.handles` to read them.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `poses.free()`
+ * once you are done using it.
+ */
+export class RigidBodyPoses {
+    raw: RawRigidBodyPoses;
+
+    /**
+     * Release the WASM memory occupied by this buffer.
+     */
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw?: RawRigidBodyPoses) {
+        this.raw = raw || new RawRigidBodyPoses();
+    }
+
+    /**
+     * The number of floats used to encode the pose of a single rigid-body.
+     */
+    public static stride(): number {
+        return RawRigidBodyPoses.stride();
+    }
+
+    /**
+     * A view of the rigid-body handles stored in a pose array returned by `data()` or `view()`.
+     *
+     * The handle of the `i`-th rigid-body is at index `i * RigidBodyPoses.stride() / 2` of the returned array.
+     *
+     * @param poses - An array returned by `data()` or `view()`.
+     */
+    public static handles(poses: Float32Array): Float64Array {
+        return new Float64Array(
+            poses.buffer,
+            poses.byteOffset,
+            poses.length / 2,
+        );
+    }
+
+    /**
+     * The handle of the `i`-th rigid-body of the given pose array.
+     *
+     * @param poses - An array returned by `data()` or `view()`.
+     * @param i - The index of the rigid-body in the pose array.
+     */
+    public static handle(poses: Float32Array, i: number): RigidBodyHandle {
+        const stride = RigidBodyPoses.stride();
+        return RigidBodyPoses.handles(poses)[(i * stride) / 2];
+    }
+
+    /**
+     * The number of rigid-body poses contained in this buffer.
+     */
+    public len(): number {
+        return this.raw.len();
+    }
+
+    /**
+     * Does this buffer contain no rigid-body pose?
+     */
+    public isEmpty(): boolean {
+        return this.raw.isEmpty();
+    }
+
+    /**
+     * A copy of the packed poses.
+     */
+    public data(): Float32Array {
+        return this.raw.data();
+    }
+
+    /**
+     * A view of the packed poses directly into the WASM memory, without any copy.
+     *
+     * The returned array must not be kept around: it is invalidated by the next `exportPoses`,
+     * by freeing this buffer, and by any other call to the physics engine (which may grow the WASM memory).
+     */
+    public view(): Float32Array {
+        return this.raw.view();
+    }
+}
@@ -11,6 +11,7 @@ import {ColliderSet} from "../geometry";
 import {ImpulseJointSet} from "./impulse_joint_set";
 import {MultibodyJointSet} from "./multibody_joint_set";
 import {IslandManager} from "./island_manager";
+import {RigidBodyPoses} from "./rigid_body_poses";
 
 /**
  * A set of rigid bodies that can be handled by a physics pipeline.
@@ -55,6 +56,23 @@ export class RigidBodySet {
         this.map.forEach((rb) => rb.finalizeDeserialization(colliderSet));
     }
 
+    /**
+     * Internal method, do not call this explicitly.
+     */
+    public mapExisting(handle: RigidBodyHandle, colliderSet: ColliderSet) {
+        this.map.set(handle, new RigidBody(this.raw, colliderSet, handle));
+    }
+
+    /** @internal */
+    public saveMap(): Coarena<RigidBody> {
+        return this.map.clone();
+    }
+
+    /** @internal */
+    public restoreMap(map: Coarena<RigidBody>) {
+        this.map = map.clone();
+    }
+
     /**
      * Creates a new rigid-body and return its integer handle.
      *
@@ -111,6 +129,9 @@ export class RigidBodySet {
             desc.status as number as RawRigidBodyType,
             desc.canSleep,
             desc.sleeping,
+            desc.normalizedLinearSleepThreshold,
+            desc.angularSleepThreshold,
+            desc.timeUntilSleep,
             desc.softCcdPrediction,
             desc.ccdEnabled,
             desc.dominanceGroup,
@@ -227,6 +248,98 @@ export class RigidBodySet {
         });
     }
 
+    /**
+     * Writes the handle, translation, and rotation of rigid-bodies into a single packed buffer.
+     *
+     * This is much faster than reading the pose of each rigid-body individually.
+     *
+     * @param out - The buffer to fill. Its previous content is overwritten.
+     * @param islands - If provided, only the active (non-sleeping) rigid-bodies are exported.
+     */
+    public exportPoses(out: RigidBodyPoses, islands?: IslandManager) {
+        if (!!islands) {
+            this.raw.exportActivePoses(islands.raw, out.raw);
+        } else {
+            this.raw.exportPoses(out.raw);
+        }
+    }
+
+    /**
+     * Sets the next kinematic translations of several rigid-bodies at once.
+     *
+     * This is equivalent to calling `RigidBody.setNextKinematicTranslation` on each rigid-body,
+     * but with a single call to the physics engine. Throws an error if the number of
+     * translations doesn’t match the number of handles.
+     *
+     * @param handles - The handles of the rigid-bodies to modify.
+     * @param translations - The packed world-space translations, with two (in 2D) or three (in 3D)
+     *                       consecutive floats per rigid-body, in the same order as `handles`.
+     */
+    public setNextKinematicTranslations(
+        handles: Float64Array,
+        translations: Float32Array,
+    ) {
+        this.raw.rbSetNextKinematicTranslations(handles, translations);
+    }
+
+    /**
+     * Sets the next kinematic rotations of several rigid-bodies at once.
+     *
+     * This is equivalent to calling `RigidBody.setNextKinematicRotation` on each rigid-body,
+     * but with a single call to the physics engine. Throws an error if the number of
+     * rotations doesn’t match the number of handles.
+     *
+     * @param handles - The handles of the rigid-bodies to modify.
+     * @param rotations - The packed world-space rotations, with one angle (in 2D) or four consecutive
+     *                    quaternion components `x, y, z, w` (in 3D) per rigid-body, in the same order as `handles`.
+     */
+    public setNextKinematicRotations(
+        handles: Float64Array,
+        rotations: Float32Array,
+    ) {
+        this.raw.rbSetNextKinematicRotations(handles, rotations);
+    }
+
+    /**
+     * Sets the linear velocities of several rigid-bodies at once.
+     *
+     * This is equivalent to calling `RigidBody.setLinvel` on each rigid-body,
+     * but with a single call to the physics engine. Throws an error if the number of
+     * linear velocities doesn’t match the number of handles.
+     *
+     * @param handles - The handles of the rigid-bodies to modify.
+     * @param linvels - The packed linear velocities, with two (in 2D) or three (in 3D)
+     *                  consecutive floats per rigid-body, in the same order as `handles`.
+     * @param wakeUp - Forces the rigid-bodies to wake-up.
+     */
+    public setLinvels(
+        handles: Float64Array,
+        linvels: Float32Array,
+        wakeUp: boolean,
+    ) {
+        this.raw.rbSetLinvels(handles, linvels, wakeUp);
+    }
+
+    /**
+     * Sets the angular velocities of several rigid-bodies at once.
+     *
+     * This is equivalent to calling `RigidBody.setAngvel` on each rigid-body,
+     * but with a single call to the physics engine. Throws an error if the number of
+     * angular velocities doesn’t match the number of handles.
+     *
+     * @param handles - The handles of the rigid-bodies to modify.
+     * @param angvels - The packed angular velocities, with one (in 2D) or three (in 3D)
+     *                  consecutive floats per rigid-body, in the same order as `handles`.
+     * @param wakeUp - Forces the rigid-bodies to wake-up.
+     */
+    public setAngvels(
+        handles: Float64Array,
+        angvels: Float32Array,
+        wakeUp: boolean,
+    ) {
+        this.raw.rbSetAngvels(handles, angvels, wakeUp);
+    }
+
     /**
      * Gets all rigid-bodies in the list.
      *
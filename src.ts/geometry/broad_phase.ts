@@ -138,6 +138,67 @@ export class BroadPhase {
         return result;
     }
 
+    /**
+     * The number of elements used to encode the result of one ray in the array returned
+     * by `castRays`.
+     */
+    public static rayHitStride(): number {
+        return RawBroadPhase.rayHitStride();
+    }
+
+    /**
+     * Find the closest intersection between each of the given rays and a set of colliders.
+     *
+     * This is much faster than calling `castRayAndGetNormal` for each ray since the query
+     * is set up only once and no object is allocated per ray.
+     *
+     * The result of the i-th ray occupies `BroadPhase.rayHitStride()` consecutive elements
+     * of the returned array:
+     * - In 2D: `[collider, timeOfImpact, nx, ny, featureType, featureId]`.
+     * - In 3D: `[collider, timeOfImpact, nx, ny, nz, featureType, featureId]`.
+     *
+     * where `n` is the normal at the hit point. If the ray doesn’t hit anything, `collider`,
+     * `timeOfImpact`, and `featureId` are set to `-1`, and `featureType` to
+     * `FeatureType.Unknown`.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param origins - The packed origins of the rays (2 or 3 elements per ray).
+     * @param dirs - The packed directions of the rays (2 or 3 elements per ray).
+     * @param maxTois - The maximum time-of-impact of each ray.
+     * @param solid - If `false` then the rays will attempt to hit the boundary of a shape, even
+     *   if their origin already lies inside of a shape.
+     * @param groups - Used to filter the colliders that can or cannot be hit by the rays.
+     */
+    public castRays(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        origins: Float32Array,
+        dirs: Float32Array,
+        maxTois: Float32Array,
+        solid: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+    ): Float64Array {
+        return this.raw.castRays(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            origins,
+            dirs,
+            maxTois,
+            solid,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+        );
+    }
+
     /**
      * Cast a ray and collects all the intersections between a ray and the scene.
      *
@@ -241,6 +302,58 @@ export class BroadPhase {
         return result;
     }
 
+    /**
+     * Gets the handle of up to one collider intersecting the given shape, for each of the
+     * given poses.
+     *
+     * This is much faster than calling `intersectionWithShape` for each pose since the
+     * query is set up only once.
+     *
+     * The i-th element of the returned array is the handle of the collider intersecting
+     * the shape at the i-th pose, or `-1` if there is none.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param translations - The packed positions of the shape (2 or 3 elements per pose).
+     * @param rotations - The packed orientations of the shape (one angle per pose in 2D, or
+     *   four quaternion components `x, y, z, w` per pose in 3D).
+     * @param shape - The shape used for the intersection tests.
+     * @param groups - The bit groups and filter associated to the shape, in order to only
+     *   test on colliders with collision groups compatible with the shape's group.
+     */
+    public intersectionWithShapes(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        translations: Float32Array,
+        rotations: Float32Array,
+        shape: Shape,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+    ): Float64Array {
+        let rawShape = shape.intoRaw();
+
+        try {
+            return this.raw.intersectionWithShapes(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                translations,
+                rotations,
+                rawShape,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+            );
+        } finally {
+            rawShape.free();
+        }
+    }
+
     /**
      * Find the projection of a point on the closest collider.
      *
@@ -439,6 +552,91 @@ export class BroadPhase {
         return result;
     }
 
+    /**
+     * The number of elements used to encode the result of one shape-cast in the array
+     * returned by `castShapes`.
+     */
+    public static shapeCastHitStride(): number {
+        return RawBroadPhase.shapeCastHitStride();
+    }
+
+    /**
+     * Casts the same shape from several poses at constant linear velocities, and retrieve
+     * the first collider hit by each cast.
+     *
+     * This is much faster than calling `castShape` for each pose since the query is set
+     * up only once and no object is allocated per cast.
+     *
+     * The result of the i-th cast occupies `BroadPhase.shapeCastHitStride()` consecutive
+     * elements of the returned array:
+     * - In 2D: `[collider, timeOfImpact, w1x, w1y, w2x, w2y, n1x, n1y, n2x, n2y]`.
+     * - In 3D: `[collider, timeOfImpact, w1x, w1y, w1z, w2x, w2y, w2z, n1x, n1y, n1z,
+     *   n2x, n2y, n2z]`.
+     *
+     * where `w1` and `n1` are the witness point and normal on the hit collider, and `w2` and
+     * `n2` the witness point and normal on the cast shape, both expressed in the local-space
+     * of their respective shape. If a cast doesn’t hit anything, `collider` and
+     * `timeOfImpact` are set to `-1`.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param translations - The packed initial positions of the shape (2 or 3 elements per
+     *   cast).
+     * @param rotations - The packed initial orientations of the shape (one angle per cast in
+     *   2D, or four quaternion components `x, y, z, w` per cast in 3D).
+     * @param velocities - The packed constant velocities of the shape (2 or 3 elements per
+     *   cast).
+     * @param shape - The shape to cast.
+     * @param targetDistance − If the shape moves closer to this distance from a collider, a hit
+     *                       will be returned.
+     * @param maxToi - The maximum time-of-impact that can be reported by each cast.
+     * @param stopAtPenetration - If set to `false`, a cast won’t immediately stop if the shape
+     *   is penetrating another shape at its starting point **and** its trajectory is such
+     *   that it’s on a path to exit that penetration state.
+     * @param groups - The bit groups and filter associated to the shape to cast, in order to only
+     *   test on colliders with collision groups compatible with this group.
+     */
+    public castShapes(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        translations: Float32Array,
+        rotations: Float32Array,
+        velocities: Float32Array,
+        shape: Shape,
+        targetDistance: number,
+        maxToi: number,
+        stopAtPenetration: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+    ): Float64Array {
+        let rawShape = shape.intoRaw();
+
+        try {
+            return this.raw.castShapes(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                translations,
+                rotations,
+                velocities,
+                rawShape,
+                targetDistance,
+                maxToi,
+                stopAtPenetration,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+            );
+        } finally {
+            rawShape.free();
+        }
+    }
+
     /**
      * Retrieve all the colliders intersecting the given shape.
      *
@@ -61,6 +61,22 @@ export class ColliderSet {
         );
     }
 
+    /** @internal */
+    public mapExisting(handle: ColliderHandle, bodies: RigidBodySet) {
+        let parent = bodies.get(this.raw.coParent(handle));
+        this.map.set(handle, new Collider(this, handle, parent));
+    }
+
+    /** @internal */
+    public saveMap(): Coarena<Collider> {
+        return this.map.clone();
+    }
+
+    /** @internal */
+    public restoreMap(map: Coarena<Collider>) {
+        this.map = map.clone();
+    }
+
     /**
      * Creates a new collider and return its integer handle.
      *
@@ -1,15 +1,308 @@
-import {RawDebugRenderPipeline} from "../raw";
+import {RawDebugRenderPipeline, RawDebugRenderStyle} from "../raw";
 import {Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
     IslandManager,
     ImpulseJointSet,
     MultibodyJointSet,
+    RigidBodyHandle,
     RigidBodySet,
 } from "../dynamics";
-import {BroadPhase, Collider, ColliderSet, NarrowPhase} from "../geometry";
+import {
+    BroadPhase,
+    Collider,
+    ColliderHandle,
+    ColliderSet,
+    InteractionGroups,
+    NarrowPhase,
+} from "../geometry";
 import {QueryFilterFlags} from "./query_pipeline";
 
+// NOTE: must match the bits in the DebugRenderMode on the Rust side.
+/**
+ * Flags indicating what part of the physics engine should be rendered by the debug-renderer.
+ */
+export enum DebugRenderMode {
+    /**
+     * Render the collider shapes.
+     */
+    COLLIDER_SHAPES = 1 << 0,
+    /**
+     * Render the local coordinate axes of rigid-bodies.
+     */
+    RIGID_BODY_AXES = 1 << 1,
+    /**
+     * Render the multibody joints.
+     */
+    MULTIBODY_JOINTS = 1 << 2,
+    /**
+     * Render the impulse joints.
+     */
+    IMPULSE_JOINTS = 1 << 3,
+    /**
+     * Render all the joints.
+     */
+    JOINTS = DebugRenderMode.MULTIBODY_JOINTS | DebugRenderMode.IMPULSE_JOINTS,
+    /**
+     * Render the solver contacts.
+     */
+    SOLVER_CONTACTS = 1 << 4,
+    /**
+     * Render the geometric contacts.
+     */
+    CONTACTS = 1 << 5,
+    /**
+     * Render the AABBs of colliders.
+     */
+    COLLIDER_AABBS = 1 << 6,
+    /**
+     * Render the triangle meshes approximating the collider shapes, for
+     * solid shading. These are output separately from the lines, in
+     * `DebugRenderBuffers.triangleVertices`, `triangleIndices`, and
+     * `triangleMeshes`. In 2D, heightfields are rendered as a thin strip
+     * below their segments.
+     */
+    COLLIDER_TRIANGLES = 1 << 7,
+}
+
+/**
+ * The kind of object a debug-render line was generated from.
+ */
+export enum DebugRenderObjectType {
+    RigidBody,
+    Collider,
+    ColliderAabb,
+    ImpulseJoint,
+    MultibodyJoint,
+    ContactPair,
+}
+
+/**
+ * A color used for debug-rendering, given as four HSLA (Hue Saturation
+ * Lightness Alpha) components. The hue is in degrees and the other
+ * components in `[0, 1]`.
+ */
+export type DebugColor = [number, number, number, number];
+
+function colorFromRaw(raw: Float32Array): DebugColor {
+    return [raw[0], raw[1], raw[2], raw[3]];
+}
+
+/**
+ * The style used for computing the line colors of the debug-render pipeline.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `style.free()`
+ * once you are done using it.
+ */
+export class DebugRenderStyle {
+    raw: RawDebugRenderStyle;
+
+    constructor(raw?: RawDebugRenderStyle) {
+        this.raw = raw || new RawDebugRenderStyle();
+    }
+
+    /**
+     * Free the WASM memory used by this style.
+     */
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    /**
+     * The number of subdivisions used to approximate the curved parts of a shape with smooth
+     * faces (default: `20`).
+     */
+    get subdivisions(): number {
+        return this.raw.subdivisions;
+    }
+
+    set subdivisions(value: number) {
+        this.raw.subdivisions = value;
+    }
+
+    /**
+     * The number of subdivisions used to approximate the curved borders of round shapes
+     * (default: `5`).
+     */
+    get borderSubdivisions(): number {
+        return this.raw.borderSubdivisions;
+    }
+
+    set borderSubdivisions(value: number) {
+        this.raw.borderSubdivisions = value;
+    }
+
+    /**
+     * The color of colliders attached to dynamic rigid-bodies.
+     */
+    get colliderDynamicColor(): DebugColor {
+        return colorFromRaw(this.raw.colliderDynamicColor);
+    }
+
+    set colliderDynamicColor(value: DebugColor) {
+        this.raw.colliderDynamicColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of colliders attached to fixed rigid-bodies.
+     */
+    get colliderFixedColor(): DebugColor {
+        return colorFromRaw(this.raw.colliderFixedColor);
+    }
+
+    set colliderFixedColor(value: DebugColor) {
+        this.raw.colliderFixedColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of colliders attached to kinematic rigid-bodies.
+     */
+    get colliderKinematicColor(): DebugColor {
+        return colorFromRaw(this.raw.colliderKinematicColor);
+    }
+
+    set colliderKinematicColor(value: DebugColor) {
+        this.raw.colliderKinematicColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of colliders not attached to any rigid-body.
+     */
+    get colliderParentlessColor(): DebugColor {
+        return colorFromRaw(this.raw.colliderParentlessColor);
+    }
+
+    set colliderParentlessColor(value: DebugColor) {
+        this.raw.colliderParentlessColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of the line between a rigid-body’s center-of-mass and the anchors of its
+     * attached impulse joints.
+     */
+    get impulseJointAnchorColor(): DebugColor {
+        return colorFromRaw(this.raw.impulseJointAnchorColor);
+    }
+
+    set impulseJointAnchorColor(value: DebugColor) {
+        this.raw.impulseJointAnchorColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of the line between the two anchors of an impulse joint.
+     */
+    get impulseJointSeparationColor(): DebugColor {
+        return colorFromRaw(this.raw.impulseJointSeparationColor);
+    }
+
+    set impulseJointSeparationColor(value: DebugColor) {
+        this.raw.impulseJointSeparationColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of the line between a rigid-body’s center-of-mass and the anchors of its
+     * attached multibody joints.
+     */
+    get multibodyJointAnchorColor(): DebugColor {
+        return colorFromRaw(this.raw.multibodyJointAnchorColor);
+    }
+
+    set multibodyJointAnchorColor(value: DebugColor) {
+        this.raw.multibodyJointAnchorColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of the line between the two anchors of a multibody joint.
+     */
+    get multibodyJointSeparationColor(): DebugColor {
+        return colorFromRaw(this.raw.multibodyJointSeparationColor);
+    }
+
+    set multibodyJointSeparationColor(value: DebugColor) {
+        this.raw.multibodyJointSeparationColor = new Float32Array(value);
+    }
+
+    /**
+     * The multiplier applied to the colors of the entities attached to a sleeping rigid-body.
+     */
+    get sleepColorMultiplier(): DebugColor {
+        return colorFromRaw(this.raw.sleepColorMultiplier);
+    }
+
+    set sleepColorMultiplier(value: DebugColor) {
+        this.raw.sleepColorMultiplier = new Float32Array(value);
+    }
+
+    /**
+     * The multiplier applied to the colors of the entities attached to a disabled rigid-body.
+     */
+    get disabledColorMultiplier(): DebugColor {
+        return colorFromRaw(this.raw.disabledColorMultiplier);
+    }
+
+    set disabledColorMultiplier(value: DebugColor) {
+        this.raw.disabledColorMultiplier = new Float32Array(value);
+    }
+
+    /**
+     * The length of the local coordinate axes rendered for a rigid-body (default: `0.5`).
+     */
+    get rigidBodyAxesLength(): number {
+        return this.raw.rigidBodyAxesLength;
+    }
+
+    set rigidBodyAxesLength(value: number) {
+        this.raw.rigidBodyAxesLength = value;
+    }
+
+    /**
+     * The color of the segments joining the two contact points.
+     */
+    get contactDepthColor(): DebugColor {
+        return colorFromRaw(this.raw.contactDepthColor);
+    }
+
+    set contactDepthColor(value: DebugColor) {
+        this.raw.contactDepthColor = new Float32Array(value);
+    }
+
+    /**
+     * The color of the contact normals.
+     */
+    get contactNormalColor(): DebugColor {
+        return colorFromRaw(this.raw.contactNormalColor);
+    }
+
+    set contactNormalColor(value: DebugColor) {
+        this.raw.contactNormalColor = new Float32Array(value);
+    }
+
+    /**
+     * The length of the contact normals (default: `0.3`).
+     */
+    get contactNormalLength(): number {
+        return this.raw.contactNormalLength;
+    }
+
+    set contactNormalLength(value: number) {
+        this.raw.contactNormalLength = value;
+    }
+
+    /**
+     * The color of the colliders’ AABBs.
+     */
+    get colliderAabbColor(): DebugColor {
+        return colorFromRaw(this.raw.colliderAabbColor);
+    }
+
+    set colliderAabbColor(value: DebugColor) {
+        this.raw.colliderAabbColor = new Float32Array(value);
+    }
+}
+
 /**
  * The vertex and color buffers for debug-redering the physics scene.
  */
@@ -27,10 +320,51 @@ export class DebugRenderBuffers {
      * has four consecutive components (in RGBA format).
      */
     public colors: Float32Array;
+    /**
+     * The objects the lines come from, if `DebugRenderPipeline.recordObjects`
+     * is enabled. Each line is described by
+     * `DebugRenderPipeline.objectStride()` consecutive elements:
+     * `[objectType, handle1, handle2]`, where `objectType` is a
+     * `DebugRenderObjectType` and `handle1` is the handle of the rigid-body,
+     * collider, impulse joint, or multibody joint the line comes from. For
+     * contact pairs, `handle1` and `handle2` are the handles of the two
+     * colliders in contact. Otherwise `handle2` is `-1`.
+     */
+    public objects?: Float64Array;
+    /**
+     * The vertices of the collider triangle meshes, in world-space, if the
+     * `DebugRenderMode.COLLIDER_TRIANGLES` mode is enabled. Each vertex is
+     * described as two (in 2D) or three (in 3D) consecutive floats.
+     */
+    public triangleVertices?: Float32Array;
+    /**
+     * The indices of the collider triangle meshes, with three consecutive
+     * indices into `triangleVertices` per triangle.
+     */
+    public triangleIndices?: Uint32Array;
+    /**
+     * The colliders the triangle meshes were generated for. Each collider is
+     * described by `DebugRenderPipeline.triangleMeshStride()` consecutive
+     * elements: `[collider, firstIndex, numIndices]`, where `firstIndex` and
+     * `numIndices` delimit the part of `triangleIndices` describing the
+     * triangles of this collider.
+     */
+    public triangleMeshes?: Float64Array;
 
-    constructor(vertices: Float32Array, colors: Float32Array) {
+    constructor(
+        vertices: Float32Array,
+        colors: Float32Array,
+        objects?: Float64Array,
+        triangleVertices?: Float32Array,
+        triangleIndices?: Uint32Array,
+        triangleMeshes?: Float64Array,
+    ) {
         this.vertices = vertices;
         this.colors = colors;
+        this.objects = objects;
+        this.triangleVertices = triangleVertices;
+        this.triangleIndices = triangleIndices;
+        this.triangleMeshes = triangleMeshes;
     }
 }
 
@@ -44,6 +378,10 @@ export class DebugRenderPipeline {
     raw: RawDebugRenderPipeline;
     public vertices: Float32Array;
     public colors: Float32Array;
+    public objects?: Float64Array;
+    public triangleVertices?: Float32Array;
+    public triangleIndices?: Uint32Array;
+    public triangleMeshes?: Float64Array;
 
     /**
      * Release the WASM memory occupied by this serialization pipeline.
@@ -55,12 +393,74 @@ export class DebugRenderPipeline {
         this.raw = undefined;
         this.vertices = undefined;
         this.colors = undefined;
+        this.objects = undefined;
+        this.triangleVertices = undefined;
+        this.triangleIndices = undefined;
+        this.triangleMeshes = undefined;
     }
 
     constructor(raw?: RawDebugRenderPipeline) {
         this.raw = raw || new RawDebugRenderPipeline();
     }
 
+    /**
+     * The flags indicating what part of the physics engine is rendered.
+     */
+    get mode(): DebugRenderMode {
+        return this.raw.mode();
+    }
+
+    set mode(mode: DebugRenderMode) {
+        this.raw.setMode(mode);
+    }
+
+    /**
+     * If `true`, the object each line comes from is recorded into `objects`
+     * when rendering (default: `false`).
+     */
+    get recordObjects(): boolean {
+        return this.raw.recordObjects();
+    }
+
+    set recordObjects(enabled: boolean) {
+        this.raw.setRecordObjects(enabled);
+    }
+
+    /**
+     * The number of elements used to encode the object of one line in
+     * `objects`.
+     */
+    public static objectStride(): number {
+        return RawDebugRenderPipeline.objectStride();
+    }
+
+    /**
+     * The number of elements used to encode one collider in `triangleMeshes`.
+     */
+    public static triangleMeshStride(): number {
+        return RawDebugRenderPipeline.triangleMeshStride();
+    }
+
+    /**
+     * Gets a copy of the style used for computing the line colors.
+     *
+     * The returned style MUST be freed manually with `style.free()` once you
+     * are done using it. Modifying it has no effect on this pipeline unless
+     * it is given back to `setStyle`.
+     */
+    public style(): DebugRenderStyle {
+        return new DebugRenderStyle(this.raw.style());
+    }
+
+    /**
+     * Sets the style used for computing the line colors.
+     *
+     * @param style - The new style. It is copied and can be freed afterwards.
+     */
+    public setStyle(style: DebugRenderStyle) {
+        this.raw.setStyle(style.raw);
+    }
+
     public render(
         bodies: RigidBodySet,
         colliders: ColliderSet,
@@ -69,6 +469,9 @@ export class DebugRenderPipeline {
         narrow_phase: NarrowPhase,
         filterFlags?: QueryFilterFlags,
         filterPredicate?: (collider: Collider) => boolean,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
     ) {
         this.raw.render(
             bodies.raw,
@@ -77,9 +480,25 @@ export class DebugRenderPipeline {
             multibody_joints.raw,
             narrow_phase.raw,
             filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
             colliders.castClosure(filterPredicate),
         );
         this.vertices = this.raw.vertices();
         this.colors = this.raw.colors();
+        this.objects = this.raw.recordObjects()
+            ? this.raw.objects()
+            : undefined;
+
+        if ((this.mode & DebugRenderMode.COLLIDER_TRIANGLES) != 0) {
+            this.triangleVertices = this.raw.triangleVertices();
+            this.triangleIndices = this.raw.triangleIndices();
+            this.triangleMeshes = this.raw.triangleMeshes();
+        } else {
+            this.triangleVertices = undefined;
+            this.triangleIndices = undefined;
+            this.triangleMeshes = undefined;
+        }
     }
 }
@@ -1,5 +1,9 @@
-import {RawContactForceEvent, RawEventQueue} from "../raw";
-import {RigidBodyHandle} from "../dynamics";
+import {
+    RawCollisionEvent,
+    RawContactForceEvent,
+    RawEventQueue,
+} from "../raw";
+import {ImpulseJointHandle, RigidBodyHandle} from "../dynamics";
 import {Collider, ColliderHandle} from "../geometry";
 import {Vector, VectorOps} from "../math";
 
@@ -18,6 +22,91 @@ export enum ActiveEvents {
     CONTACT_FORCE_EVENTS = 0b0010,
 }
 
+/**
+ * Flags providing more information regarding a collision event.
+ */
+export enum CollisionEventFlags {
+    NONE = 0,
+    /**
+     * Set if at least one of the colliders involved in the collision was a sensor
+     * when the event was fired.
+     */
+    SENSOR = 0b0001,
+    /**
+     * Set if a stopped collision event was fired because at least one of the colliders
+     * involved was removed.
+     */
+    REMOVED = 0b0010,
+}
+
+/**
+ * Event occurring when two colliders start or stop colliding.
+ *
+ * This object should **not** be stored anywhere. Its properties can only be
+ * read from within the closure given to `EventHandler.drainCollisionEventObjects`.
+ */
+export class TempCollisionEvent {
+    raw: RawCollisionEvent;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    /**
+     * The first collider involved in the collision.
+     */
+    public collider1(): ColliderHandle {
+        return this.raw.collider1();
+    }
+
+    /**
+     * The second collider involved in the collision.
+     */
+    public collider2(): ColliderHandle {
+        return this.raw.collider2();
+    }
+
+    /**
+     * Did the two colliders start colliding?
+     */
+    public started(): boolean {
+        return this.raw.started();
+    }
+
+    /**
+     * Did the two colliders stop colliding?
+     */
+    public stopped(): boolean {
+        return this.raw.stopped();
+    }
+
+    /**
+     * The flags providing more information regarding this event.
+     */
+    public flags(): CollisionEventFlags {
+        return this.raw.flags();
+    }
+
+    /**
+     * Was at least one of the colliders involved in the collision a sensor
+     * when the event was fired?
+     */
+    public sensor(): boolean {
+        return this.raw.sensor();
+    }
+
+    /**
+     * Was this stopped collision event fired because at least one of the colliders
+     * involved was removed?
+     */
+    public removed(): boolean {
+        return this.raw.removed();
+    }
+}
+
 /**
  * Event occurring when the sum of the magnitudes of the
  * contact forces between two colliders exceed a threshold.
@@ -133,6 +222,25 @@ export class EventQueue {
         this.raw.drainCollisionEvents(f);
     }
 
+    /**
+     * Applies the given javascript closure on each collision event of this collector, then clear
+     * the internal collision event buffer.
+     *
+     * Unlike `drainCollisionEvents`, this gives access to the event flags, e.g., to distinguish
+     * sensor events, or stopped events caused by the removal of a collider.
+     *
+     * @param f - JavaScript closure applied to each collision event. The
+     *            closure must take one `TempCollisionEvent` argument.
+     */
+    public drainCollisionEventObjects(f: (event: TempCollisionEvent) => void) {
+        let event = new TempCollisionEvent();
+        this.raw.drainCollisionEventObjects((raw: RawCollisionEvent) => {
+            event.raw = raw;
+            f(event);
+            event.free();
+        });
+    }
+
     /**
      * Applies the given javascript closure on each contact force event of this collector, then clear
      * the internal collision event buffer.
@@ -149,6 +257,134 @@ export class EventQueue {
         });
     }
 
+    /**
+     * Applies the given javascript closure on each sleep event of this collector, then clear
+     * the internal sleep event buffer.
+     *
+     * A sleep event is generated whenever a dynamic rigid-body falls asleep or wakes up during
+     * a `world.step` performed with this collector. A dynamic rigid-body inserted awake generates
+     * a wake-up event at its first step. Removed rigid-bodies, and rigid-bodies put to sleep
+     * manually with `RigidBody.sleep`, don’t generate any event.
+     *
+     * @param f - JavaScript closure applied to each sleep event. The closure must take two
+     * arguments: the handle of the rigid-body, and a boolean indicating if it fell asleep (true)
+     * or woke up (false).
+     */
+    public drainSleepEvents(
+        f: (handle: RigidBodyHandle, sleeping: boolean) => void,
+    ) {
+        this.raw.drainSleepEvents(f);
+    }
+
+    /**
+     * Applies the given javascript closure on each joint break event of this collector, then
+     * clear the internal joint break event buffer.
+     *
+     * A joint break event is generated whenever a breakable impulse joint (see
+     * `ImpulseJoint.setBreakThresholds`) breaks during a `world.step` performed with this
+     * collector. The broken joint is disabled, not removed.
+     *
+     * @param f - JavaScript closure applied to each joint break event. The closure must take
+     * three arguments: the handle of the joint that broke, and the handles of the two
+     * rigid-bodies it is attached to.
+     */
+    public drainJointBreakEvents(
+        f: (
+            joint: ImpulseJointHandle,
+            body1: RigidBodyHandle,
+            body2: RigidBodyHandle,
+        ) => void,
+    ) {
+        this.raw.drainJointBreakEvents(f);
+    }
+
+    /**
+     * The number of elements used to encode one event in the array returned by
+     * `drainCollisionEventsPacked`.
+     */
+    public static collisionEventStride(): number {
+        return RawEventQueue.collisionEventStride();
+    }
+
+    /**
+     * The number of elements used to encode one event in the array returned by
+     * `drainContactForceEventsPacked`.
+     */
+    public static contactForceEventStride(): number {
+        return RawEventQueue.contactForceEventStride();
+    }
+
+    /**
+     * The number of elements used to encode one event in the array returned by
+     * `drainSleepEventsPacked`.
+     */
+    public static sleepEventStride(): number {
+        return RawEventQueue.sleepEventStride();
+    }
+
+    /**
+     * The number of elements used to encode one event in the array returned by
+     * `drainJointBreakEventsPacked`.
+     */
+    public static jointBreakEventStride(): number {
+        return RawEventQueue.jointBreakEventStride();
+    }
+
+    /**
+     * Returns all the collision events of this collector packed into a single array, then clear
+     * the internal collision event buffer.
+     *
+     * This is much faster than `drainCollisionEvents` when a lot of events are generated since
+     * it doesn’t involve a JavaScript call per event.
+     *
+     * Each event occupies `EventQueue.collisionEventStride()` consecutive elements:
+     * `[collider1, collider2, started, flags]` where `started` is `1` if the collision
+     * started and `0` if it stopped, and `flags` is a bitmask of `CollisionEventFlags`.
+     */
+    public drainCollisionEventsPacked(): Float64Array {
+        return this.raw.drainCollisionEventsPacked();
+    }
+
+    /**
+     * Returns all the contact force events of this collector packed into a single array, then clear
+     * the internal contact force event buffer.
+     *
+     * This is much faster than `drainContactForceEvents` when a lot of events are generated since
+     * it doesn’t involve a JavaScript call per event.
+     *
+     * Each event occupies `EventQueue.contactForceEventStride()` consecutive elements:
+     * - In 2D: `[collider1, collider2, fx, fy, totalForceMagnitude, dx, dy, maxForceMagnitude]`.
+     * - In 3D: `[collider1, collider2, fx, fy, fz, totalForceMagnitude, dx, dy, dz, maxForceMagnitude]`.
+     *
+     * where `f` is the total force, and `d` is the max force direction.
+     */
+    public drainContactForceEventsPacked(): Float64Array {
+        return this.raw.drainContactForceEventsPacked();
+    }
+
+    /**
+     * Returns all the sleep events of this collector packed into a single array, then clear
+     * the internal sleep event buffer.
+     *
+     * Each event occupies `EventQueue.sleepEventStride()` consecutive elements:
+     * `[body, sleeping]` where `sleeping` is `1` if the rigid-body fell asleep and `0` if it
+     * woke up.
+     */
+    public drainSleepEventsPacked(): Float64Array {
+        return this.raw.drainSleepEventsPacked();
+    }
+
+    /**
+     * Returns all the joint break events of this collector packed into a single array, then
+     * clear the internal joint break event buffer.
+     *
+     * Each event occupies `EventQueue.jointBreakEventStride()` consecutive elements:
+     * `[joint, body1, body2]`.
+     */
+    public drainJointBreakEventsPacked(): Float64Array {
+        return this.raw.drainJointBreakEventsPacked();
+    }
+
     /**
      * Removes all events contained by this collector
      */
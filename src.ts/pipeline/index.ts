@@ -1,6 +1,8 @@
 export * from "./world";
 export * from "./physics_pipeline";
 export * from "./serialization_pipeline";
+export * from "./world_checkpoint";
+export * from "./world_hash";
 export * from "./event_queue";
 export * from "./physics_hooks";
 export * from "./debug_render_pipeline";
@@ -1,11 +1,13 @@
+import {RawContactModificationContext} from "../raw";
 import {RigidBodyHandle} from "../dynamics";
 import {ColliderHandle} from "../geometry";
+import {Vector, VectorOps} from "../math";
 
 export enum ActiveHooks {
     NONE = 0,
     FILTER_CONTACT_PAIRS = 0b0001,
     FILTER_INTERSECTION_PAIRS = 0b0010,
-    // MODIFY_SOLVER_CONTACTS = 0b0100, /* Not supported yet in JS. */
+    MODIFY_SOLVER_CONTACTS = 0b0100,
 }
 
 export enum SolverFlags {
@@ -51,4 +53,200 @@ export interface PhysicsHooks {
         body1: RigidBodyHandle,
         body2: RigidBodyHandle,
     ): boolean;
+
+    /**
+     * Function that modifies the set of contacts seen by the constraints solver.
+     *
+     * This will only be executed and taken into account if at least one of the involved colliders contains the
+     * `ActiveHooks.MODIFY_SOLVER_CONTACTS` flag in its active hooks.
+     *
+     * This can be used to implement one-way platforms, conveyor belts, or per-contact friction
+     * and restitution overrides.
+     *
+     * @param context − The contact modification context. It is only valid during this call and must not be
+     *                  kept around after this function returns.
+     */
+    modifySolverContacts?(context: ContactModificationContext): void;
+}
+
+/**
+ * Read/write access to the contacts of a single contact manifold, given to the
+ * `PhysicsHooks.modifySolverContacts` hook.
+ *
+ * This is only valid during the execution of the hook it was given to.
+ */
+export class ContactModificationContext {
+    raw: RawContactModificationContext;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw: RawContactModificationContext) {
+        this.raw = raw;
+    }
+
+    /**
+     * The handle of the first collider involved in the contact.
+     */
+    public collider1(): ColliderHandle {
+        return this.raw.collider1();
+    }
+
+    /**
+     * The handle of the second collider involved in the contact.
+     */
+    public collider2(): ColliderHandle {
+        return this.raw.collider2();
+    }
+
+    /**
+     * The handle of the rigid-body the first collider is attached to, if any.
+     */
+    public rigidBody1(): RigidBodyHandle | undefined {
+        return this.raw.rigid_body1();
+    }
+
+    /**
+     * The handle of the rigid-body the second collider is attached to, if any.
+     */
+    public rigidBody2(): RigidBodyHandle | undefined {
+        return this.raw.rigid_body2();
+    }
+
+    /**
+     * The world-space contact normal, pointing outward from the first collider.
+     */
+    public normal(): Vector {
+        return VectorOps.fromRaw(this.raw.normal());
+    }
+
+    /**
+     * Sets the world-space contact normal used by the constraints solver.
+     */
+    public setNormal(normal: Vector) {
+        let rawNormal = VectorOps.intoRaw(normal);
+        this.raw.set_normal(rawNormal);
+        rawNormal.free();
+    }
+
+    /**
+     * The contact normal in the local-space of the first collider.
+     */
+    public localNormal1(): Vector {
+        return VectorOps.fromRaw(this.raw.local_n1());
+    }
+
+    /**
+     * The contact normal in the local-space of the second collider.
+     */
+    public localNormal2(): Vector {
+        return VectorOps.fromRaw(this.raw.local_n2());
+    }
+
+    /**
+     * User-defined data attached to this contact manifold. It is preserved across
+     * timesteps as long as the manifold exists.
+     */
+    public userData(): number {
+        return this.raw.user_data();
+    }
+
+    public setUserData(userData: number) {
+        this.raw.set_user_data(userData);
+    }
+
+    public numSolverContacts(): number {
+        return this.raw.num_solver_contacts();
+    }
+
+    /**
+     * Removes all the solver contacts so that this contact manifold is ignored by the constraints solver.
+     */
+    public clearSolverContacts() {
+        this.raw.clear_solver_contacts();
+    }
+
+    /**
+     * Removes the `i`-th solver contact. The last solver contact is moved to index `i`.
+     */
+    public removeSolverContact(i: number) {
+        this.raw.remove_solver_contact(i);
+    }
+
+    public solverContactPoint(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.solver_contact_point(i));
+    }
+
+    public setSolverContactPoint(i: number, point: Vector) {
+        let rawPoint = VectorOps.intoRaw(point);
+        this.raw.set_solver_contact_point(i, rawPoint);
+        rawPoint.free();
+    }
+
+    public solverContactDist(i: number): number {
+        return this.raw.solver_contact_dist(i);
+    }
+
+    public setSolverContactDist(i: number, dist: number) {
+        this.raw.set_solver_contact_dist(i, dist);
+    }
+
+    public solverContactFriction(i: number): number {
+        return this.raw.solver_contact_friction(i);
+    }
+
+    public setSolverContactFriction(i: number, friction: number) {
+        this.raw.set_solver_contact_friction(i, friction);
+    }
+
+    public solverContactRestitution(i: number): number {
+        return this.raw.solver_contact_restitution(i);
+    }
+
+    public setSolverContactRestitution(i: number, restitution: number) {
+        this.raw.set_solver_contact_restitution(i, restitution);
+    }
+
+    public solverContactTangentVelocity(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.solver_contact_tangent_velocity(i));
+    }
+
+    /**
+     * Sets the desired relative tangent velocity at the `i`-th solver contact, e.g., to simulate a conveyor belt.
+     */
+    public setSolverContactTangentVelocity(i: number, velocity: Vector) {
+        let rawVel = VectorOps.intoRaw(velocity);
+        this.raw.set_solver_contact_tangent_velocity(i, rawVel);
+        rawVel.free();
+    }
+
+    /**
+     * Is the `i`-th solver contact new, i.e., did it not exist at the previous timestep?
+     */
+    public solverContactIsNew(i: number): boolean {
+        return this.raw.solver_contact_is_new(i);
+    }
+
+    /**
+     * Updates the solver contacts to emulate a one-way platform.
+     *
+     * Contacts are only kept if the contact normal, expressed in the local-space of the first
+     * collider, is within `allowedAngle` of `allowedLocalNormal1`. This relies on the
+     * manifold’s user-data, so it must not be modified by the hook when using this method.
+     *
+     * @param allowedLocalNormal1 - The allowed contact normal, in the local-space of the first collider.
+     * @param allowedAngle - The maximum angle, in radians, between the contact normal and `allowedLocalNormal1`.
+     */
+    public updateAsOneWayPlatform(
+        allowedLocalNormal1: Vector,
+        allowedAngle: number,
+    ) {
+        let rawNormal = VectorOps.intoRaw(allowedLocalNormal1);
+        this.raw.update_as_oneway_platform(rawNormal, allowedAngle);
+        rawNormal.free();
+    }
 }
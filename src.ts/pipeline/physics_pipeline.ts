@@ -1,4 +1,4 @@
-import {RawPhysicsPipeline} from "../raw";
+import {RawContactModificationContext, RawPhysicsPipeline} from "../raw";
 import {Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
@@ -16,10 +16,11 @@ import {
     NarrowPhase,
 } from "../geometry";
 import {EventQueue} from "./event_queue";
-import {PhysicsHooks} from "./physics_hooks";
+import {ContactModificationContext, PhysicsHooks} from "./physics_hooks";
 
 export class PhysicsPipeline {
     raw: RawPhysicsPipeline;
+    tempContactModificationContext: ContactModificationContext;
 
     public free() {
         if (!!this.raw) {
@@ -30,6 +31,9 @@ export class PhysicsPipeline {
 
     constructor(raw?: RawPhysicsPipeline) {
         this.raw = raw || new RawPhysicsPipeline();
+        this.tempContactModificationContext = new ContactModificationContext(
+            null,
+        );
     }
 
     public step(
@@ -49,6 +53,22 @@ export class PhysicsPipeline {
         let rawG = VectorOps.intoRaw(gravity);
 
         if (!!eventQueue) {
+            let modifySolverContacts =
+                !!hooks && !!hooks.modifySolverContacts
+                    ? (rawContext: RawContactModificationContext) => {
+                          this.tempContactModificationContext.raw = rawContext;
+                          try {
+                              hooks.modifySolverContacts(
+                                  this.tempContactModificationContext,
+                              );
+                          } finally {
+                              // SAFETY: the raw context is invalidated once
+                              //         the hook returns, even if it throws.
+                              this.tempContactModificationContext.free();
+                          }
+                      }
+                    : null;
+
             this.raw.stepWithEvents(
                 rawG,
                 integrationParameters.raw,
@@ -64,6 +84,7 @@ export class PhysicsPipeline {
                 hooks,
                 !!hooks ? hooks.filterContactPair : null,
                 !!hooks ? hooks.filterIntersectionPair : null,
+                modifySolverContacts,
             );
         } else {
             this.raw.step(
@@ -1,15 +1,93 @@
-import {RawSerializationPipeline} from "../raw";
+import {
+    RawDeltaSnapshotBase,
+    RawPrefabHandleMap,
+    RawSerializationPipeline,
+} from "../raw";
 import {Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
     IslandManager,
+    ImpulseJointHandle,
     ImpulseJointSet,
+    MultibodyJointHandle,
     MultibodyJointSet,
+    RigidBodyHandle,
     RigidBodySet,
 } from "../dynamics";
-import {BroadPhase, ColliderSet, NarrowPhase} from "../geometry";
+import {
+    BroadPhase,
+    ColliderHandle,
+    ColliderSet,
+    NarrowPhase,
+} from "../geometry";
 import {World} from "./world";
 
+/**
+ * The handles of the objects inserted by `SerializationPipeline.insertPrefab`.
+ *
+ * Each map associates the handle an object had when the prefab was created
+ * to the handle of the corresponding newly inserted object.
+ */
+export class PrefabHandleMap {
+    bodies: Map<RigidBodyHandle, RigidBodyHandle>;
+    colliders: Map<ColliderHandle, ColliderHandle>;
+    impulseJoints: Map<ImpulseJointHandle, ImpulseJointHandle>;
+    multibodyJoints: Map<MultibodyJointHandle, MultibodyJointHandle>;
+
+    constructor(raw: RawPrefabHandleMap) {
+        this.bodies = PrefabHandleMap.pairsToMap(raw.bodies());
+        this.colliders = PrefabHandleMap.pairsToMap(raw.colliders());
+        this.impulseJoints = PrefabHandleMap.pairsToMap(raw.impulseJoints());
+        this.multibodyJoints = PrefabHandleMap.pairsToMap(
+            raw.multibodyJoints(),
+        );
+    }
+
+    private static pairsToMap(pairs: Float64Array): Map<number, number> {
+        let result = new Map<number, number>();
+        for (let i = 0; i < pairs.length; i += 2) {
+            result.set(pairs[i], pairs[i + 1]);
+        }
+        return result;
+    }
+}
+
+/**
+ * A full snapshot prepared for computing and restoring delta snapshots.
+ *
+ * Preparing the base is about as expensive as restoring a full snapshot, so the
+ * same base should be reused for as many delta snapshots as possible.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `base.free()`
+ * once you are done using it.
+ */
+export class DeltaSnapshotBase {
+    raw: RawDeltaSnapshotBase;
+
+    /**
+     * Release the WASM memory occupied by this delta snapshot base.
+     */
+    free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    /**
+     * Prepares a snapshot for being used as the base of delta snapshots.
+     *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid snapshot,
+     * or if it was created by a different version or flavor (dimension and feature set)
+     * of the library.
+     *
+     * @param snapshot - A snapshot created by `World.takeSnapshot`.
+     */
+    constructor(snapshot: Uint8Array) {
+        this.raw = new RawDeltaSnapshotBase(snapshot);
+    }
+}
+
 /**
  * A pipeline for serializing the physics scene.
  *
@@ -35,6 +113,10 @@ export class SerializationPipeline {
 
     /**
      * Serialize a complete physics state into a single byte array.
+     *
+     * The result starts with a header identifying the version and flavor (dimension and
+     * feature set) of the library that created it. It can only be deserialized by the
+     * exact same version and flavor.
      * @param gravity - The current gravity affecting the simulation.
      * @param integrationParameters - The integration parameters of the simulation.
      * @param broadPhase - The broad-phase of the simulation.
@@ -76,9 +158,218 @@ export class SerializationPipeline {
     /**
      * Deserialize the complete physics state from a single byte array.
      *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid snapshot,
+     * or if it was created by a different version or flavor (dimension and feature set)
+     * of the library.
+     *
      * @param data - The byte array to deserialize.
      */
     public deserializeAll(data: Uint8Array): World {
         return World.fromRaw(this.raw.deserializeAll(data));
     }
+
+    /**
+     * Serialize the difference between the current physics state and a base snapshot.
+     *
+     * Only the parts of the state that changed since the base snapshot are stored. In
+     * particular, rigid-bodies and colliders that didn’t change don’t take any space.
+     * The broad-phase and narrow-phase are stored too, so the contacts are restored
+     * exactly, but they usually change completely whenever contacts are added or removed.
+     *
+     * @param base - The base snapshot the delta is computed from.
+     * @param gravity - The current gravity affecting the simulation.
+     * @param integrationParameters - The integration parameters of the simulation.
+     * @param islands - The island manager of the simulation.
+     * @param broadPhase - The broad-phase of the simulation.
+     * @param narrowPhase - The narrow-phase of the simulation.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param multibodyJoints - The multibody joints taking part into the simulation.
+     */
+    public serializeDelta(
+        base: DeltaSnapshotBase,
+        gravity: Vector,
+        integrationParameters: IntegrationParameters,
+        islands: IslandManager,
+        broadPhase: BroadPhase,
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): Uint8Array {
+        let rawGra = VectorOps.intoRaw(gravity);
+
+        const res = this.raw.serializeDelta(
+            base.raw,
+            rawGra,
+            integrationParameters.raw,
+            islands.raw,
+            broadPhase.raw,
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+        );
+        rawGra.free();
+
+        return res;
+    }
+
+    /**
+     * Deserialize the complete physics state from a delta snapshot and its base.
+     *
+     * The resulting physics state is bit-for-bit identical to the serialized state.
+     *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid delta
+     * snapshot, if it was computed from a different base, or if it was created by a
+     * different version or flavor (dimension and feature set) of the library.
+     *
+     * @param base - The base snapshot the delta was computed from.
+     * @param data - The delta snapshot to deserialize.
+     */
+    public deserializeDelta(base: DeltaSnapshotBase, data: Uint8Array): World {
+        return World.fromRaw(this.raw.deserializeDelta(base.raw, data));
+    }
+
+    /**
+     * Export the rigid-bodies, colliders, joints, and integration parameters into a
+     * JSON scene.
+     *
+     * Unlike snapshots, the scene only contains the parameters the objects would be created
+     * with (the same as the ones of `RigidBodyDesc`, `ColliderDesc`, and the joint
+     * descriptions), so it can be written or edited by hand. Contacts, islands, and the
+     * internal solver state are not exported.
+     *
+     * Throws an `Error` if a collider has a compound or custom shape.
+     *
+     * @param gravity - The current gravity affecting the simulation.
+     * @param integrationParameters - The integration parameters of the simulation.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param multibodyJoints - The multibody joints taking part into the simulation.
+     */
+    public serializeScene(
+        gravity: Vector,
+        integrationParameters: IntegrationParameters,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): string {
+        let rawGra = VectorOps.intoRaw(gravity);
+
+        const res = this.raw.serializeScene(
+            rawGra,
+            integrationParameters.raw,
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+        );
+        rawGra.free();
+
+        return res;
+    }
+
+    /**
+     * Create a new physics world from a JSON scene.
+     *
+     * Every field of the rigid-bodies, colliders, and joints of the scene is optional (except
+     * the shape of colliders and the rigid-bodies attached to joints) and defaults to the
+     * same value as for their descriptions.
+     *
+     * Throws an `Error` with a human-readable message if the scene is invalid or was written
+     * for a different dimension.
+     *
+     * @param json - The JSON scene to import.
+     */
+    public deserializeScene(json: string): World {
+        return World.fromRaw(this.raw.deserializeScene(json));
+    }
+
+    /**
+     * Serialize a subset of the rigid-bodies, with their colliders and the joints between them,
+     * into a single byte array.
+     *
+     * Unlike `serializeAll`, this doesn’t include the broad-phase, narrow-phase, and island
+     * manager. The result can be inserted into any physics world with `insertPrefab`.
+     * Joints attached to a rigid-body that isn’t part of `handles` are ignored.
+     *
+     * Throws an `Error` if one of the handles doesn’t match any rigid-body.
+     *
+     * @param handles - The handles of the rigid-bodies to serialize.
+     * @param bodies - The set containing the rigid-bodies to serialize.
+     * @param colliders - The set containing the colliders attached to these rigid-bodies.
+     * @param impulseJoints - The set containing the impulse joints between these rigid-bodies.
+     * @param multibodyJoints - The set containing the multibody joints between these rigid-bodies.
+     */
+    public serializePrefab(
+        handles: RigidBodyHandle[],
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): Uint8Array {
+        return this.raw.serializePrefab(
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+            new Float64Array(handles),
+        );
+    }
+
+    /**
+     * Insert the rigid-bodies, colliders, and joints of a prefab created by `serializePrefab`.
+     *
+     * The inserted objects are given new handles. Multibody joints are inserted with their
+     * initial (zero) configuration.
+     *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid prefab,
+     * or if it was created by a different version or flavor (dimension and feature set)
+     * of the library.
+     *
+     * @param data - The byte array to deserialize.
+     * @param bodies - The set where the rigid-bodies are inserted.
+     * @param colliders - The set where the colliders are inserted.
+     * @param impulseJoints - The set where the impulse joints are inserted.
+     * @param multibodyJoints - The set where the multibody joints are inserted.
+     * @returns The mapping from the handles in the prefab to the handles of the inserted objects.
+     */
+    public insertPrefab(
+        data: Uint8Array,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): PrefabHandleMap {
+        const rawMap = this.raw.insertPrefab(
+            data,
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+        );
+        const result = new PrefabHandleMap(rawMap);
+        rawMap.free();
+
+        result.bodies.forEach((handle) =>
+            bodies.mapExisting(handle, colliders),
+        );
+        result.colliders.forEach((handle) =>
+            colliders.mapExisting(handle, bodies),
+        );
+        result.impulseJoints.forEach((handle) =>
+            impulseJoints.mapExisting(handle, bodies),
+        );
+        result.multibodyJoints.forEach((handle) =>
+            multibodyJoints.mapExisting(handle, bodies),
+        );
+
+        return result;
+    }
 }
@@ -12,6 +12,7 @@ import {
     RawRigidBodySet,
     RawSerializationPipeline,
     RawDebugRenderPipeline,
+    RawWorldStateHash,
 } from "../raw";
 
 import {
@@ -34,8 +35,10 @@ import {
     CCDSolver,
     IntegrationParameters,
     IslandManager,
+    Islands,
     ImpulseJoint,
     ImpulseJointHandle,
+    InverseKinematicsOptions,
     MultibodyJoint,
     MultibodyJointHandle,
     JointData,
@@ -49,7 +52,13 @@ import {
 import {Rotation, Vector, VectorOps} from "../math";
 import {PhysicsPipeline} from "./physics_pipeline";
 import {QueryFilterFlags} from "./query_pipeline";
-import {SerializationPipeline} from "./serialization_pipeline";
+import {
+    DeltaSnapshotBase,
+    PrefabHandleMap,
+    SerializationPipeline,
+} from "./serialization_pipeline";
+import {WorldCheckpoint} from "./world_checkpoint";
+import {WorldStateHash} from "./world_hash";
 import {EventQueue} from "./event_queue";
 import {PhysicsHooks} from "./physics_hooks";
 import {DebugRenderBuffers, DebugRenderPipeline} from "./debug_render_pipeline";
@@ -181,6 +190,7 @@ export class World {
         // #endif
 
         this.impulseJoints.finalizeDeserialization(this.bodies);
+        this.multibodyJoints.finalizeDeserialization(this.bodies);
         this.bodies.finalizeDeserialization(this.colliders);
         this.colliders.finalizeDeserialization(this.bodies);
     }
@@ -225,22 +235,246 @@ export class World {
      * Creates a new physics world from a snapshot.
      *
      * This new physics world will be an identical copy of the snapshoted physics world.
+     *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid snapshot,
+     * or if it was created by a different version or flavor (dimension and feature set)
+     * of the library.
      */
     public static restoreSnapshot(data: Uint8Array): World {
         let deser = new SerializationPipeline();
         return deser.deserializeAll(data);
     }
 
+    /**
+     * Computes a stable hash of the current state of this world.
+     *
+     * The hash covers the poses and velocities of the rigid-bodies, the poses of the
+     * colliders, and the state of the joints. With the deterministic builds of the library,
+     * clients simulating the same world get the same hash at each step, so comparing hashes
+     * allows to detect desynchronizations.
+     *
+     * @param perBodyHashes - If `true`, the hash of each rigid-body is also computed separately
+     *                        to help identifying the rigid-bodies that diverged.
+     */
+    public computeStateHash(perBodyHashes?: boolean): WorldStateHash {
+        const rawHash = new RawWorldStateHash(
+            this.bodies.raw,
+            this.colliders.raw,
+            this.impulseJoints.raw,
+            this.multibodyJoints.raw,
+            !!perBodyHashes,
+        );
+        const result = new WorldStateHash(rawHash);
+        rawHash.free();
+        return result;
+    }
+
+    /**
+     * Saves the current state of this world into a checkpoint kept in WASM memory.
+     *
+     * This is much faster than `.takeSnapshot()` since it doesn’t involve any serialization
+     * nor any copy between JS and WASM. Use `.restoreCheckpoint` to bring this world back
+     * to the saved state.
+     *
+     * @param checkpoint - The checkpoint to overwrite. Reusing a checkpoint avoids memory
+     *                     allocations. If not provided, a new checkpoint is created.
+     */
+    public saveCheckpoint(checkpoint?: WorldCheckpoint): WorldCheckpoint {
+        checkpoint = checkpoint || new WorldCheckpoint();
+        checkpoint.raw.save(
+            this.integrationParameters.raw,
+            this.islands.raw,
+            this.broadPhase.raw,
+            this.narrowPhase.raw,
+            this.bodies.raw,
+            this.colliders.raw,
+            this.impulseJoints.raw,
+            this.multibodyJoints.raw,
+        );
+        checkpoint.gravity = checkpoint.gravity || VectorOps.zeros();
+        VectorOps.copy(checkpoint.gravity, this.gravity);
+        checkpoint.bodies = this.bodies.saveMap();
+        checkpoint.colliders = this.colliders.saveMap();
+        checkpoint.impulseJoints = this.impulseJoints.saveMap();
+        checkpoint.multibodyJoints = this.multibodyJoints.saveMap();
+        return checkpoint;
+    }
+
+    /**
+     * Restores, in place, the state of this world saved in the given checkpoint.
+     *
+     * The checkpoint is left unchanged, so it can be restored several times. It must
+     * have been created by `.saveCheckpoint()` on this same world. Rigid-bodies, colliders,
+     * and joints removed since the checkpoint was saved are restored with the same handles.
+     *
+     * @param checkpoint - The checkpoint to restore.
+     * @returns `false` (without modifying this world) if the checkpoint is empty.
+     */
+    public restoreCheckpoint(checkpoint: WorldCheckpoint): boolean {
+        const restored = checkpoint.raw.restore(
+            this.integrationParameters.raw,
+            this.islands.raw,
+            this.broadPhase.raw,
+            this.narrowPhase.raw,
+            this.bodies.raw,
+            this.colliders.raw,
+            this.impulseJoints.raw,
+            this.multibodyJoints.raw,
+        );
+
+        if (restored) {
+            this.gravity = VectorOps.zeros();
+            VectorOps.copy(this.gravity, checkpoint.gravity);
+            this.bodies.restoreMap(checkpoint.bodies);
+            this.colliders.restoreMap(checkpoint.colliders);
+            this.impulseJoints.restoreMap(checkpoint.impulseJoints);
+            this.multibodyJoints.restoreMap(checkpoint.multibodyJoints);
+        }
+
+        return restored;
+    }
+
+    /**
+     * Takes a delta snapshot of this world, containing only the difference between its
+     * current state and the given base snapshot.
+     *
+     * This is typically much smaller than a full snapshot when only a few rigid-bodies
+     * and colliders changed since the base snapshot was taken. Use `World.restoreDeltaSnapshot`
+     * with the same base to create a new physics world with a state identical to the state when
+     * `.takeDeltaSnapshot()` is called.
+     *
+     * @param base - The base snapshot, prepared from a snapshot created by `.takeSnapshot()`.
+     */
+    public takeDeltaSnapshot(base: DeltaSnapshotBase): Uint8Array {
+        return this.serializationPipeline.serializeDelta(
+            base,
+            this.gravity,
+            this.integrationParameters,
+            this.islands,
+            this.broadPhase,
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    /**
+     * Creates a new physics world from a delta snapshot and the base it was computed from.
+     *
+     * The state of the new physics world is bit-for-bit identical to the state of the
+     * snapshoted physics world. With the deterministic builds of the library, simulating
+     * it gives the same results on all platforms.
+     *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid delta
+     * snapshot, if it was computed from a different base, or if it was created by a
+     * different version or flavor (dimension and feature set) of the library.
+     */
+    public static restoreDeltaSnapshot(
+        base: DeltaSnapshotBase,
+        data: Uint8Array,
+    ): World {
+        let deser = new SerializationPipeline();
+        let world = deser.deserializeDelta(base, data);
+        deser.free();
+        return world;
+    }
+
+    /**
+     * Exports this world as a JSON scene.
+     *
+     * The scene describes the rigid-bodies, colliders, joints, and integration parameters
+     * with the same parameters as their descriptions (`RigidBodyDesc`, `ColliderDesc`, and
+     * the joint descriptions), so it can be written or edited by hand and doesn’t depend on
+     * the version of the library. Use `World.importScene` to create a new physics world
+     * from it.
+     *
+     * Rigid-bodies are referred to by their index in the `bodies` array of the scene. Contacts
+     * and the internal solver state are not exported, so simulating the imported world may
+     * give slightly different results than simulating this world.
+     */
+    public exportScene(): string {
+        return this.serializationPipeline.serializeScene(
+            this.gravity,
+            this.integrationParameters,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    /**
+     * Creates a new physics world from a JSON scene exported by `.exportScene()` or
+     * written by hand.
+     *
+     * Throws an `Error` with a human-readable message if the scene is invalid or was written
+     * for a different dimension.
+     */
+    public static importScene(json: string): World {
+        let deser = new SerializationPipeline();
+        let world = deser.deserializeScene(json);
+        deser.free();
+        return world;
+    }
+
+    /**
+     * Serializes the given rigid-bodies, with their colliders and the joints between them,
+     * into a prefab.
+     *
+     * Use `World.insertPrefab` to insert copies of these objects into any physics world.
+     *
+     * @param handles - The handles of the rigid-bodies to serialize.
+     */
+    public serializePrefab(handles: RigidBodyHandle[]): Uint8Array {
+        return this.serializationPipeline.serializePrefab(
+            handles,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    /**
+     * Inserts the rigid-bodies, colliders, and joints of a prefab created by
+     * `World.serializePrefab` into this physics world.
+     *
+     * Throws an `Error` with a human-readable message if the data isn’t a valid prefab,
+     * or if it was created by a different version or flavor (dimension and feature set)
+     * of the library.
+     *
+     * @param data - The prefab to insert.
+     * @returns The mapping from the handles in the prefab to the handles of the inserted objects.
+     */
+    public insertPrefab(data: Uint8Array): PrefabHandleMap {
+        return this.serializationPipeline.insertPrefab(
+            data,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
     /**
      * Computes all the lines (and their colors) needed to render the scene.
      *
      * @param filterFlags - Flags for excluding whole subsets of colliders from rendering.
      * @param filterPredicate - Any collider for which this closure returns `false` will be excluded from the
      *                          debug rendering.
+     * @param filterGroups - If set, only the colliders with collision groups compatible with these
+     *                       groups will be rendered.
+     * @param filterExcludeCollider - A collider excluded from the debug rendering.
+     * @param filterExcludeRigidBody - A rigid-body whose colliders are excluded from the debug rendering.
      */
     public debugRender(
         filterFlags?: QueryFilterFlags,
         filterPredicate?: (collider: Collider) => boolean,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
     ): DebugRenderBuffers {
         this.debugRenderPipeline.render(
             this.bodies,
@@ -250,10 +484,17 @@ export class World {
             this.narrowPhase,
             filterFlags,
             filterPredicate,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
         );
         return new DebugRenderBuffers(
             this.debugRenderPipeline.vertices,
             this.debugRenderPipeline.colors,
+            this.debugRenderPipeline.objects,
+            this.debugRenderPipeline.triangleVertices,
+            this.debugRenderPipeline.triangleIndices,
+            this.debugRenderPipeline.triangleMeshes,
         );
     }
 
@@ -583,6 +824,7 @@ export class World {
         wakeUp: boolean,
     ): MultibodyJoint {
         return this.multibodyJoints.createJoint(
+            this.bodies,
             params,
             parent1.handle,
             parent2.handle,
@@ -590,6 +832,78 @@ export class World {
         );
     }
 
+    /**
+     * Moves, with inverse kinematics, the multibody link the given joint is
+     * attached to at the given pose.
+     *
+     * Returns the joint displacements computed by the solver, in generalized
+     * coordinates, with one value per degree of freedom of the multibody.
+     *
+     * @param joint - The joint attached to the multibody link to move.
+     * @param targetTranslation - The desired world-space translation of the
+     *   link.
+     * @param targetRotation - The desired world-space rotation of the link.
+     * @param options - The options of the inverse-kinematics solver.
+     * @param apply - If `false`, the displacements are only computed without
+     *   being applied to the multibody. If `true`, the rigid-bodies of the
+     *   multibody are also woken up.
+     */
+    public multibodyInverseKinematics(
+        joint: MultibodyJoint,
+        targetTranslation: Vector,
+        targetRotation: Rotation,
+        options?: InverseKinematicsOptions,
+        apply: boolean = true,
+    ): Float32Array {
+        return this.multibodyJoints.inverseKinematics(
+            this.bodies,
+            joint.handle,
+            targetTranslation,
+            targetRotation,
+            options,
+            apply,
+        );
+    }
+
+    /**
+     * The mass matrix, in generalized coordinates, of the multibody the given
+     * joint is part of.
+     *
+     * This is a symmetric matrix with one row and one column per degree of
+     * freedom of the multibody, packed in column-major order.
+     *
+     * @param joint - A joint of the multibody.
+     */
+    public multibodyMassMatrix(joint: MultibodyJoint): Float32Array {
+        return this.multibodyJoints.massMatrix(this.bodies, joint.handle);
+    }
+
+    /**
+     * Applies a generalized impulse to the multibody the given joint is part
+     * of.
+     *
+     * Throws an error if the mass matrix of the multibody can’t be inverted,
+     * which is always the case before the first timestep following the
+     * creation of the multibody.
+     *
+     * @param joint - A joint of the multibody.
+     * @param impulses - The generalized impulse, with one value per degree
+     *   of freedom of the multibody.
+     * @param wakeUp - Should the rigid-bodies of the multibody be awakened?
+     */
+    public applyMultibodyGeneralizedImpulse(
+        joint: MultibodyJoint,
+        impulses: Float32Array,
+        wakeUp: boolean,
+    ) {
+        this.multibodyJoints.applyGeneralizedImpulse(
+            this.bodies,
+            joint.handle,
+            impulses,
+            wakeUp,
+        );
+    }
+
     /**
      * Retrieves a rigid-body from its handle.
      *
@@ -718,6 +1032,27 @@ export class World {
         this.bodies.forEachActiveRigidBody(this.islands, f);
     }
 
+    /**
+     * Computes the islands of this physics world, i.e., the sets of dynamic rigid-bodies
+     * connected, directly or indirectly, by active contacts or enabled joints.
+     *
+     * The islands are reconstructed from the current contact and joint graphs; they
+     * are not the islands used internally by the physics pipeline for solving and
+     * sleeping.
+     *
+     * The returned islands MUST be freed manually with `islands.free()` once you are done
+     * using them.
+     */
+    public computeIslands(): Islands {
+        return this.islands.islands(
+            this.bodies,
+            this.colliders,
+            this.narrowPhase,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
     /**
      * Find the closest intersection between a ray and the physics world.
      *
@@ -792,6 +1127,47 @@ export class World {
         );
     }
 
+    /**
+     * Find the closest intersection between each of the given rays and the physics world.
+     *
+     * This is much faster than calling `castRayAndGetNormal` for each ray. See
+     * `BroadPhase.castRays` for the layout of the returned array, and
+     * `BroadPhase.rayHitStride()` for the number of elements per ray.
+     *
+     * @param origins - The packed origins of the rays (2 or 3 elements per ray).
+     * @param dirs - The packed directions of the rays (2 or 3 elements per ray).
+     * @param maxTois - The maximum time-of-impact of each ray.
+     * @param solid - If `false` then the rays will attempt to hit the boundary of a shape, even
+     *   if their origin already lies inside of a shape.
+     * @param groups - Used to filter the colliders that can or cannot be hit by the rays.
+     */
+    public castRays(
+        origins: Float32Array,
+        dirs: Float32Array,
+        maxTois: Float32Array,
+        solid: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+    ): Float64Array {
+        return this.broadPhase.castRays(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            origins,
+            dirs,
+            maxTois,
+            solid,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+        );
+    }
+
     /**
      * Cast a ray and collects all the intersections between a ray and the scene.
      *
@@ -867,6 +1243,46 @@ export class World {
         return handle != null ? this.colliders.get(handle) : null;
     }
 
+    /**
+     * Gets the handle of up to one collider intersecting the given shape, for each of the
+     * given poses.
+     *
+     * This is much faster than calling `intersectionWithShape` for each pose. The i-th
+     * element of the returned array is the handle of the collider intersecting the shape at
+     * the i-th pose, or `-1` if there is none.
+     *
+     * @param translations - The packed positions of the shape (2 or 3 elements per pose).
+     * @param rotations - The packed orientations of the shape (one angle per pose in 2D, or
+     *   four quaternion components `x, y, z, w` per pose in 3D).
+     * @param shape - The shape used for the intersection tests.
+     * @param groups - The bit groups and filter associated to the shape, in order to only
+     *   test on colliders with collision groups compatible with the shape's group.
+     */
+    public intersectionWithShapes(
+        translations: Float32Array,
+        rotations: Float32Array,
+        shape: Shape,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+    ): Float64Array {
+        return this.broadPhase.intersectionWithShapes(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            translations,
+            rotations,
+            shape,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+        );
+    }
+
     /**
      * Find the projection of a point on the closest collider.
      *
@@ -1014,6 +1430,63 @@ export class World {
         );
     }
 
+    /**
+     * Casts the same shape from several poses at constant linear velocities, and retrieve
+     * the first collider hit by each cast.
+     *
+     * This is much faster than calling `castShape` for each pose. See
+     * `BroadPhase.castShapes` for the layout of the returned array, and
+     * `BroadPhase.shapeCastHitStride()` for the number of elements per cast.
+     *
+     * @param translations - The packed initial positions of the shape (2 or 3 elements per
+     *   cast).
+     * @param rotations - The packed initial orientations of the shape (one angle per cast in
+     *   2D, or four quaternion components `x, y, z, w` per cast in 3D).
+     * @param velocities - The packed constant velocities of the shape (2 or 3 elements per
+     *   cast).
+     * @param shape - The shape to cast.
+     * @param targetDistance − If the shape moves closer to this distance from a collider, a hit
+     *                         will be returned.
+     * @param maxToi - The maximum time-of-impact that can be reported by each cast.
+     * @param stopAtPenetration - If set to `false`, a cast won’t immediately stop if the shape
+     *   is penetrating another shape at its starting point **and** its trajectory is such
+     *   that it’s on a path to exit that penetration state.
+     * @param groups - The bit groups and filter associated to the shape to cast, in order to only
+     *   test on colliders with collision groups compatible with this group.
+     */
+    public castShapes(
+        translations: Float32Array,
+        rotations: Float32Array,
+        velocities: Float32Array,
+        shape: Shape,
+        targetDistance: number,
+        maxToi: number,
+        stopAtPenetration: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+    ): Float64Array {
+        return this.broadPhase.castShapes(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            translations,
+            rotations,
+            velocities,
+            shape,
+            targetDistance,
+            maxToi,
+            stopAtPenetration,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+        );
+    }
+
     /**
      * Retrieve all the colliders intersecting the given shape.
      *
@@ -0,0 +1,58 @@
+import {RawWorldCheckpoint} from "../raw";
+import {Coarena} from "../coarena";
+import {Vector} from "../math";
+import {ImpulseJoint, MultibodyJoint, RigidBody} from "../dynamics";
+import {Collider} from "../geometry";
+
+/**
+ * A copy of the state of a physics world, kept in WASM memory.
+ *
+ * Unlike snapshots, saving and restoring a checkpoint doesn’t involve any serialization
+ * nor any copy between JS and WASM, making it suitable for saving and restoring the
+ * state of a world several times per frame (e.g. for rollback or prediction).
+ * Use `World.saveCheckpoint` and `World.restoreCheckpoint` to save and restore it.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `checkpoint.free()`
+ * once you are done using it.
+ */
+export class WorldCheckpoint {
+    raw: RawWorldCheckpoint;
+    /** @internal */
+    gravity: Vector;
+    /** @internal */
+    bodies: Coarena<RigidBody>;
+    /** @internal */
+    colliders: Coarena<Collider>;
+    /** @internal */
+    impulseJoints: Coarena<ImpulseJoint>;
+    /** @internal */
+    multibodyJoints: Coarena<MultibodyJoint>;
+
+    /**
+     * Release the WASM memory occupied by this checkpoint.
+     */
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+        this.bodies = undefined;
+        this.colliders = undefined;
+        this.impulseJoints = undefined;
+        this.multibodyJoints = undefined;
+    }
+
+    /**
+     * Creates a new empty checkpoint.
+     */
+    constructor() {
+        this.raw = new RawWorldCheckpoint();
+    }
+
+    /**
+     * Is this checkpoint empty, i.e., was no state saved into it yet?
+     */
+    public isEmpty(): boolean {
+        return this.raw.isEmpty();
+    }
+}
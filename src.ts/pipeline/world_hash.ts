@@ -0,0 +1,63 @@
+import {RawWorldStateHash} from "../raw";
+import {RigidBodyHandle} from "../dynamics";
+
+function toHex32(value: number): string {
+    return ("00000000" + value.toString(16)).slice(-8);
+}
+
+/**
+ * A hash of the state of a physics world, computed by `World.computeStateHash`.
+ *
+ * The hash is computed from the exact bits of the rigid-body poses and velocities,
+ * collider poses, and joint states. Comparing the hashes computed by several clients
+ * running the deterministic builds of the library at the same simulation step allows
+ * to detect when their simulations diverged.
+ */
+export class WorldStateHash {
+    /**
+     * The hash of the whole physics state, as a 16-digits hexadecimal string.
+     */
+    hash: string;
+    /**
+     * The hash of each rigid-body’s pose and velocities, as 16-digits hexadecimal
+     * strings. This is empty unless per-body hashes were requested.
+     */
+    bodyHashes: Map<RigidBodyHandle, string>;
+
+    constructor(raw: RawWorldStateHash) {
+        this.hash = raw.hash();
+        this.bodyHashes = new Map<RigidBodyHandle, string>();
+
+        const handles = raw.bodyHandles();
+        const hashes = raw.bodyHashes();
+        for (let i = 0; i < handles.length; ++i) {
+            this.bodyHashes.set(
+                handles[i],
+                toHex32(hashes[i * 2 + 1]) + toHex32(hashes[i * 2]),
+            );
+        }
+    }
+
+    /**
+     * Returns the handles of the rigid-bodies with a different hash in `this` and `other`,
+     * including the rigid-bodies that only exist in one of them.
+     *
+     * Both hashes must have been computed with per-body hashes.
+     *
+     * @param other - The hash to compare with.
+     */
+    public divergedBodies(other: WorldStateHash): RigidBodyHandle[] {
+        let result: RigidBodyHandle[] = [];
+        this.bodyHashes.forEach((hash, handle) => {
+            if (other.bodyHashes.get(handle) !== hash) {
+                result.push(handle);
+            }
+        });
+        other.bodyHashes.forEach((_, handle) => {
+            if (!this.bodyHashes.has(handle)) {
+                result.push(handle);
+            }
+        });
+        return result;
+    }
+}
@@ -1,4 +1,7 @@
-use crate::dynamics::{RawImpulseJointSet, RawJointAxis, RawJointType, RawMotorModel};
+use crate::dynamics::{
+    joint_impulses, JointBreakThreshold, RawImpulseJointSet, RawJointAxis, RawJointType,
+    RawMotorModel,
+};
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
 use rapier::dynamics::JointAxis;
@@ -73,6 +76,74 @@ impl RawImpulseJointSet {
         });
     }
 
+    /// Is this joint enabled?
+    ///
+    /// A disabled joint doesn’t apply any force to the rigid-bodies it is attached to.
+    pub fn jointIsEnabled(&self, handle: FlatHandle) -> bool {
+        self.map(handle, |j| j.data.is_enabled())
+    }
+
+    /// Enables or disables this joint.
+    pub fn jointSetEnabled(&mut self, handle: FlatHandle, enabled: bool) {
+        self.map_mut(handle, |j| {
+            j.data.set_enabled(enabled);
+        });
+    }
+
+    /// The total impulse applied by this joint along each of its axes during the last substep of
+    /// the last step, including the impulses applied by its limits and motors.
+    ///
+    /// The solver splits each step into substeps (one per solver iteration) and only keeps the
+    /// impulses of the last one. The force applied by the joint is approximately this impulse
+    /// divided by the substep length, i.e., `dt / numSolverIterations`.
+    ///
+    /// The impulses are expressed in the local frame of the joint attached to the first
+    /// rigid-body: the linear impulses along the `x`, `y` (and `z` in 3D) axes first, followed by
+    /// the angular impulse (2D) or the angular impulses around the `x`, `y`, and `z` axes (3D).
+    pub fn jointImpulses(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map(handle, |j| joint_impulses(j).iter().copied().collect())
+    }
+
+    /// Makes this joint breakable.
+    ///
+    /// At the end of each step, the joint is disabled, and a joint break event is generated, if
+    /// the magnitude of the linear force it applied exceeds `maxForce`, or if the magnitude of
+    /// the torque it applied exceeds `maxTorque`. Set both to `Infinity` to make the joint
+    /// unbreakable.
+    pub fn jointSetBreakThresholds(&mut self, handle: FlatHandle, maxForce: f32, maxTorque: f32) {
+        let handle = utils::impulse_joint_handle(handle);
+
+        if maxForce == f32::INFINITY && maxTorque == f32::INFINITY {
+            self.1.remove(&handle.0);
+        } else if self.0.contains(handle) {
+            let threshold = JointBreakThreshold {
+                max_force: maxForce,
+                max_torque: maxTorque,
+            };
+            self.1.insert(handle.0, threshold);
+        }
+    }
+
+    /// The magnitude of the linear force above which this joint breaks, or `Infinity` if it
+    /// isn’t breakable.
+    pub fn jointBreakForce(&self, handle: FlatHandle) -> f32 {
+        let handle = utils::impulse_joint_handle(handle);
+        self.1
+            .get(&handle.0)
+            .map(|t| t.max_force)
+            .unwrap_or(f32::INFINITY)
+    }
+
+    /// The magnitude of the torque above which this joint breaks, or `Infinity` if it isn’t
+    /// breakable.
+    pub fn jointBreakTorque(&self, handle: FlatHandle) -> f32 {
+        let handle = utils::impulse_joint_handle(handle);
+        self.1
+            .get(&handle.0)
+            .map(|t| t.max_torque)
+            .unwrap_or(f32::INFINITY)
+    }
+
     /// Are the limits for this joint enabled?
     pub fn jointLimitsEnabled(&self, handle: FlatHandle, axis: RawJointAxis) -> bool {
         self.map(handle, |j| {
@@ -1,10 +1,37 @@
 use crate::dynamics::RawGenericJoint;
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::{ImpulseJoint, ImpulseJointSet};
+use rapier::data::Index;
+use rapier::dynamics::{
+    ImpulseJoint, ImpulseJointHandle, ImpulseJointSet, IntegrationParameters, RigidBodySet,
+};
+use rapier::math::{Real, SpacialVector, DIM};
+use std::collections::BTreeMap;
 use wasm_bindgen::prelude::*;
 
+/// The force and torque above which a breakable impulse joint breaks.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct JointBreakThreshold {
+    pub(crate) max_force: f32,
+    pub(crate) max_torque: f32,
+}
+
+/// The break thresholds of the breakable impulse joints, indexed by joint handle.
+pub(crate) type JointBreakThresholds = BTreeMap<Index, JointBreakThreshold>;
+
+/// The total impulse applied by the given joint along each of its axes during the last substep
+/// of the last step, including the impulses of its limits and motors.
+pub(crate) fn joint_impulses(joint: &ImpulseJoint) -> SpacialVector<Real> {
+    let mut impulses = joint.impulses;
+
+    for (i, impulse) in impulses.iter_mut().enumerate() {
+        *impulse += joint.data.limits[i].impulse + joint.data.motors[i].impulse;
+    }
+
+    impulses
+}
+
 #[wasm_bindgen]
-pub struct RawImpulseJointSet(pub(crate) ImpulseJointSet);
+pub struct RawImpulseJointSet(pub(crate) ImpulseJointSet, pub(crate) JointBreakThresholds);
 
 impl RawImpulseJointSet {
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&ImpulseJoint) -> T) -> T {
@@ -27,13 +54,68 @@ impl RawImpulseJointSet {
             );
         f(body)
     }
+
+    /// Disables every breakable joint for which the force or torque applied during the last
+    /// step exceeded its break threshold.
+    ///
+    /// The solver only keeps the impulses of the last substep, so the forces are computed by
+    /// dividing these impulses by the substep length, i.e., the timestep length divided by the
+    /// number of solver iterations (including the additional solver iterations of the attached
+    /// rigid-bodies).
+    ///
+    /// Broken joints stop being breakable. Returns the handles of the joints that broke.
+    pub(crate) fn break_joints(
+        &mut self,
+        bodies: &RigidBodySet,
+        params: &IntegrationParameters,
+    ) -> Vec<ImpulseJointHandle> {
+        let mut broken = vec![];
+        let joints = &mut self.0;
+
+        self.1.retain(|handle, threshold| {
+            let handle = ImpulseJointHandle(*handle);
+            let joint = match joints.get(handle) {
+                Some(joint) => joint,
+                // The joint was removed.
+                None => return false,
+            };
+
+            if !joint.data.is_enabled() || params.dt == 0.0 {
+                return true;
+            }
+
+            let additional_iterations = [joint.body1, joint.body2]
+                .iter()
+                .filter_map(|handle| bodies.get(*handle))
+                .map(|rb| rb.additional_solver_iterations())
+                .max()
+                .unwrap_or(0);
+            let num_substeps = params.num_solver_iterations + additional_iterations;
+            let dt = params.dt / num_substeps as Real;
+            let impulses = joint_impulses(joint);
+            let force = impulses.fixed_rows::<DIM>(0).norm() / dt;
+            let torque = impulses.rows(DIM, impulses.len() - DIM).norm() / dt;
+
+            if force > threshold.max_force || torque > threshold.max_torque {
+                if let Some(joint) = joints.get_mut(handle, true) {
+                    joint.data.set_enabled(false);
+                }
+                broken.push(handle);
+                false
+            } else {
+                true
+            }
+        });
+
+        broken
+    }
 }
 
 #[wasm_bindgen]
 impl RawImpulseJointSet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawImpulseJointSet(ImpulseJointSet::new())
+        RawImpulseJointSet(ImpulseJointSet::new(), JointBreakThresholds::new())
     }
 
     pub fn createJoint(
@@ -58,6 +140,7 @@ impl RawImpulseJointSet {
     pub fn remove(&mut self, handle: FlatHandle, wakeUp: bool) {
         let handle = utils::impulse_joint_handle(handle);
         self.0.remove(handle, wakeUp);
+        self.1.remove(&handle.0);
     }
 
     pub fn len(&self) -> usize {
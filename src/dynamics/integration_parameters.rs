@@ -56,6 +56,16 @@ impl RawIntegrationParameters {
         self.0.length_unit
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn jointNaturalFrequency(&self) -> f32 {
+        self.0.joint_natural_frequency
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jointDampingRatio(&self) -> f32 {
+        self.0.joint_damping_ratio
+    }
+
     #[wasm_bindgen(setter)]
     pub fn set_dt(&mut self, value: f32) {
         self.0.dt = value;
@@ -98,4 +108,14 @@ impl RawIntegrationParameters {
     pub fn set_lengthUnit(&mut self, value: f32) {
         self.0.length_unit = value
     }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_jointNaturalFrequency(&mut self, value: f32) {
+        self.0.joint_natural_frequency = value
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_jointDampingRatio(&mut self, value: f32) {
+        self.0.joint_damping_ratio = value
+    }
 }
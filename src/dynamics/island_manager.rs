@@ -1,5 +1,8 @@
-use crate::utils;
-use rapier::dynamics::IslandManager;
+use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
+use crate::geometry::{RawColliderSet, RawNarrowPhase};
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{IslandManager, RigidBodyHandle};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -28,4 +31,165 @@ impl RawIslandManager {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// Computes the islands of the given physics state.
+    ///
+    /// An island is a set of dynamic rigid-bodies connected, directly or indirectly, by active
+    /// contacts or enabled joints. Interactions with fixed or kinematic rigid-bodies don’t connect
+    /// islands, and sleeping rigid-bodies are included.
+    ///
+    /// These islands are reconstructed from the contact and joint graphs of the given sets each
+    /// time this is called. They are not the islands the physics pipeline uses internally for
+    /// solving and sleeping (those are not exposed by Rapier, only cover awake rigid-bodies, and
+    /// may be split or merged differently), and the state of this island manager isn’t read.
+    pub fn islands(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        narrow_phase: &RawNarrowPhase,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> RawIslands {
+        let mut handles = vec![];
+        let mut ids = HashMap::new();
+        for (handle, rb) in bodies.0.iter() {
+            if rb.is_dynamic() {
+                ids.insert(handle, handles.len());
+                handles.push(handle);
+            }
+        }
+
+        let mut components = UnionFind::new(handles.len());
+        let mut connect = |body1: RigidBodyHandle, body2: RigidBodyHandle| {
+            if let (Some(id1), Some(id2)) = (ids.get(&body1), ids.get(&body2)) {
+                components.union(*id1, *id2);
+            }
+        };
+
+        for pair in narrow_phase.0.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            let parent1 = colliders.0.get(pair.collider1).and_then(|co| co.parent());
+            let parent2 = colliders.0.get(pair.collider2).and_then(|co| co.parent());
+            if let (Some(body1), Some(body2)) = (parent1, parent2) {
+                connect(body1, body2);
+            }
+        }
+
+        for (_, joint) in impulse_joints.0.iter() {
+            if joint.data.is_enabled() {
+                connect(joint.body1, joint.body2);
+            }
+        }
+
+        for multibody in multibody_joints.0.multibodies() {
+            for link in multibody.links() {
+                let parent = link.parent_id().and_then(|id| multibody.link(id));
+                if let Some(parent) = parent {
+                    if link.joint().data.is_enabled() {
+                        connect(parent.rigid_body_handle(), link.rigid_body_handle());
+                    }
+                }
+            }
+        }
+
+        // Number the islands in the order of their first rigid-body.
+        let mut island_ids = HashMap::new();
+        let mut island_bodies: Vec<Vec<RigidBodyHandle>> = vec![];
+        let mut body_islands = HashMap::new();
+        for (id, handle) in handles.iter().enumerate() {
+            let root = components.find(id);
+            let island_id = *island_ids.entry(root).or_insert_with(|| {
+                island_bodies.push(vec![]);
+                island_bodies.len() - 1
+            });
+            island_bodies[island_id].push(*handle);
+            body_islands.insert(*handle, island_id);
+        }
+
+        let mut result = RawIslands {
+            body_handles: vec![],
+            island_offsets: vec![0],
+            island_sleeping: vec![],
+            body_islands,
+        };
+
+        for island in island_bodies {
+            let sleeping = island.iter().all(|handle| bodies.0[*handle].is_sleeping());
+            result
+                .body_handles
+                .extend(island.iter().map(|handle| utils::flat_handle(handle.0)));
+            result.island_offsets.push(result.body_handles.len());
+            result.island_sleeping.push(sleeping);
+        }
+
+        result
+    }
+}
+
+/// The islands of a physics state, computed by `RawIslandManager.islands`.
+#[wasm_bindgen]
+pub struct RawIslands {
+    /// The handles of the rigid-bodies of all the islands, grouped by island.
+    body_handles: Vec<FlatHandle>,
+    /// The range of `body_handles` containing the rigid-bodies of the i-th island is
+    /// `island_offsets[i]..island_offsets[i + 1]`.
+    island_offsets: Vec<usize>,
+    island_sleeping: Vec<bool>,
+    body_islands: HashMap<RigidBodyHandle, usize>,
+}
+
+#[wasm_bindgen]
+impl RawIslands {
+    /// The number of islands.
+    pub fn numIslands(&self) -> usize {
+        self.island_sleeping.len()
+    }
+
+    /// The handles of the rigid-bodies of the given island.
+    pub fn islandBodies(&self, island: usize) -> Vec<FlatHandle> {
+        match self.island_offsets.get(island..island + 2) {
+            Some(range) => self.body_handles[range[0]..range[1]].to_vec(),
+            None => vec![],
+        }
+    }
+
+    /// Are all the rigid-bodies of the given island sleeping?
+    pub fn isIslandSleeping(&self, island: usize) -> bool {
+        self.island_sleeping.get(island).copied().unwrap_or(false)
+    }
+
+    /// The island the given rigid-body belongs to, if it is dynamic.
+    pub fn bodyIsland(&self, handle: FlatHandle) -> Option<usize> {
+        self.body_islands.get(&utils::body_handle(handle)).copied()
+    }
+}
+
+/// A disjoint-set forest, used to compute the connected components of the interaction graph.
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut id: usize) -> usize {
+        while self.parents[id] != id {
+            self.parents[id] = self.parents[self.parents[id]];
+            id = self.parents[id];
+        }
+        id
+    }
+
+    fn union(&mut self, id1: usize, id2: usize) {
+        let root1 = self.find(id1);
+        let root2 = self.find(id2);
+        self.parents[root1.max(root2)] = root1.min(root2);
+    }
 }
@@ -167,6 +167,52 @@ impl RawGenericJoint {
         Some(Self(joint))
     }
 
+    /// Sets the limits of the joint along the given axis.
+    ///
+    /// The axis doesn’t need to be free: this can be used to limit the free axes of any kind of
+    /// joint.
+    pub fn setLimits(&mut self, axis: RawJointAxis, min: f32, max: f32) {
+        self.0.set_limits(axis.into(), [min, max]);
+    }
+
+    /// Sets the spring-like model used by the motor along the given axis.
+    pub fn setMotorModel(&mut self, axis: RawJointAxis, model: RawMotorModel) {
+        self.0.set_motor_model(axis.into(), model.into());
+    }
+
+    /// Configures the motor along the given axis.
+    ///
+    /// The motor drives the joint coordinate along this axis towards `targetPos` with the given
+    /// `stiffness`, and its velocity towards `targetVel` with the given `damping`. Setting a zero
+    /// `targetPos` and `targetVel` turns it into a spring with the given stiffness and damping.
+    pub fn setMotor(
+        &mut self,
+        axis: RawJointAxis,
+        targetPos: f32,
+        targetVel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.0
+            .set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
+    }
+
+    /// Sets the maximum force the motor along the given axis can deliver.
+    pub fn setMotorMaxForce(&mut self, axis: RawJointAxis, maxForce: f32) {
+        self.0.set_motor_max_force(axis.into(), maxForce);
+    }
+
+    /// Sets the coupled axes of the joint, as a `JointAxesMask` bit mask.
+    ///
+    /// The bits depend on the dimension: in 2D, the angular axis is `1 << 2`.
+    ///
+    /// Only the limits and motor of the first coupled linear (resp. angular) axis are applied, to
+    /// all the coupled linear (resp. angular) axes at once. For example, a rope joint couples all
+    /// its linear axes so that the limit of `LinX` applies to the distance between its anchors.
+    pub fn setCoupledAxes(&mut self, coupledAxes: u8) {
+        self.0.coupled_axes = JointAxesMask::from_bits_truncate(coupledAxes);
+    }
+
     pub fn spring(
         rest_length: f32,
         stiffness: f32,
@@ -6,6 +6,7 @@ pub use self::integration_parameters::*;
 pub use self::island_manager::*;
 pub use self::joint::*;
 pub use self::multibody_joint_set::*;
+pub use self::rigid_body_batch::*;
 pub use self::rigid_body_set::*;
 
 mod ccd_solver;
@@ -14,7 +15,9 @@ mod impulse_joint_set;
 mod integration_parameters;
 mod island_manager;
 mod joint;
+mod multibody;
 mod multibody_joint;
 mod multibody_joint_set;
 mod rigid_body;
+mod rigid_body_batch;
 mod rigid_body_set;
@@ -0,0 +1,194 @@
+use crate::dynamics::multibody_joint::push_joint_coords;
+use crate::dynamics::{RawMultibodyJointSet, RawRigidBodySet};
+use crate::utils::{self, FlatHandle};
+use na::{DMatrix, DVector};
+use rapier::dynamics::{Multibody, RigidBodySet};
+use rapier::math::{Real, SPATIAL_DIM};
+#[cfg(feature = "dim3")]
+use rapier::math::{ANG_DIM, DIM};
+use wasm_bindgen::prelude::*;
+
+/// Computes the mass matrix of the given multibody, in generalized coordinates.
+fn mass_matrix(bodies: &RigidBodySet, multibody: &Multibody) -> DMatrix<Real> {
+    let ndofs = multibody.ndofs();
+    let mut result = DMatrix::zeros(ndofs, ndofs);
+
+    for link in multibody.links() {
+        let jacobian = multibody.body_jacobian(link.link_id());
+        let rb = match bodies.get(link.rigid_body_handle()) {
+            // The jacobians are empty until the first timestep after the link was added.
+            Some(rb) if jacobian.ncols() == ndofs => rb,
+            _ => continue,
+        };
+        let mprops = rb.mass_properties();
+        let mass = mprops.effective_mass();
+        let mut rb_mass_matrix = na::SMatrix::<Real, SPATIAL_DIM, SPATIAL_DIM>::zeros();
+
+        for (i, m) in mass.iter().enumerate() {
+            rb_mass_matrix[(i, i)] = *m;
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            rb_mass_matrix[(2, 2)] = mprops.effective_angular_inertia();
+        }
+        #[cfg(feature = "dim3")]
+        {
+            let i = mprops.effective_angular_inertia();
+            rb_mass_matrix
+                .fixed_view_mut::<ANG_DIM, ANG_DIM>(DIM, DIM)
+                .copy_from(&na::Matrix3::new(
+                    i.m11, i.m12, i.m13, i.m12, i.m22, i.m23, i.m13, i.m23, i.m33,
+                ));
+        }
+
+        result.quadform(1.0, &rb_mass_matrix, jacobian, 1.0);
+    }
+
+    result
+}
+
+#[wasm_bindgen]
+impl RawMultibodyJointSet {
+    /// The number of degrees of freedom of the multibody the given joint is part of.
+    pub fn multibodyNdofs(&self, handle: FlatHandle) -> usize {
+        self.map_link(handle, |multibody, _| multibody.ndofs())
+    }
+
+    /// The handles of the rigid-bodies of each link of the multibody the given joint is part of.
+    ///
+    /// The links are given in the same order as their degrees of freedom in the generalized
+    /// coordinates, velocities, jacobians, and mass matrix of the multibody, starting with the
+    /// root link.
+    pub fn multibodyLinkBodies(&self, handle: FlatHandle) -> Vec<FlatHandle> {
+        self.map_link(handle, |multibody, _| {
+            multibody
+                .links()
+                .map(|link| utils::flat_handle(link.rigid_body_handle().0))
+                .collect()
+        })
+    }
+
+    /// The generalized coordinates of the multibody the given joint is part of.
+    ///
+    /// This is the concatenation of the coordinates of the joint of each link (see `jointCoords`),
+    /// in the order given by `multibodyLinkBodies`.
+    pub fn multibodyGeneralizedCoords(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map_link(handle, |multibody, _| {
+            let mut coords = Vec::with_capacity(multibody.ndofs());
+
+            for link in multibody.links() {
+                push_joint_coords(link.joint(), &mut coords);
+            }
+
+            coords
+        })
+    }
+
+    /// The generalized velocities of the multibody the given joint is part of.
+    pub fn multibodyGeneralizedVelocities(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map_link(handle, |multibody, _| {
+            multibody.generalized_velocity().iter().copied().collect()
+        })
+    }
+
+    /// The jacobian of the link the given joint is attached to.
+    ///
+    /// The jacobian maps the generalized velocities of the multibody to the world-space linear
+    /// velocity (at the center of mass) and angular velocity of the link’s rigid-body. It is
+    /// given as a column-major matrix with `3` rows in 2D (linear x, y, then angular) or `6` rows
+    /// in 3D (linear x, y, z, then angular x, y, z), and one column per degree of freedom of the
+    /// multibody.
+    ///
+    /// The jacobians are updated at each timestep.
+    pub fn multibodyLinkJacobian(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map_link(handle, |multibody, link| {
+            multibody.body_jacobian(link.link_id()).as_slice().to_vec()
+        })
+    }
+
+    /// The mass matrix of the multibody the given joint is part of, in generalized coordinates.
+    ///
+    /// This is a symmetric square matrix with one row and one column per degree of freedom of the
+    /// multibody, computed from the current link jacobians and rigid-body mass properties. It
+    /// doesn’t include the joint damping.
+    pub fn multibodyMassMatrix(&self, bodies: &RawRigidBodySet, handle: FlatHandle) -> Vec<f32> {
+        self.map_link(handle, |multibody, _| {
+            mass_matrix(&bodies.0, multibody).as_slice().to_vec()
+        })
+    }
+
+    /// Applies a generalized impulse to the multibody the given joint is part of.
+    ///
+    /// The generalized velocities of the multibody are increased by the product of the inverse
+    /// of its mass matrix by `impulses`. The degrees of freedom of kinematic links are not
+    /// affected. To apply a generalized force during the next timestep, apply an impulse equal to
+    /// the force multiplied by the timestep length.
+    ///
+    /// Returns an error, without modifying the multibody, if its mass matrix can’t be inverted.
+    /// This is always the case before the first timestep following the creation of the multibody
+    /// since its jacobians aren’t computed yet.
+    ///
+    /// # Parameters
+    /// - `impulses`: the generalized impulse, with one value per degree of freedom of the
+    ///   multibody.
+    /// - `wakeUp`: if `true`, the rigid-bodies of the multibody are woken up.
+    pub fn multibodyApplyGeneralizedImpulse(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        impulses: &[f32],
+        wakeUp: bool,
+    ) -> Result<(), JsError> {
+        let (multibody, _) = self
+            .0
+            .get_mut(utils::multibody_joint_handle(handle))
+            .expect("Invalid Joint reference. It may have been removed from the physics World.");
+
+        if impulses.len() != multibody.ndofs() {
+            return Err(JsError::new(&format!(
+                "Expected {} generalized impulses, got {}.",
+                multibody.ndofs(),
+                impulses.len()
+            )));
+        }
+
+        let mut free_dofs = vec![];
+        let mut first_dof = 0;
+
+        for link in multibody.links() {
+            let ndofs = link.joint().ndofs();
+            if !link.joint().kinematic {
+                free_dofs.extend(first_dof..first_dof + ndofs);
+            }
+            first_dof += ndofs;
+        }
+
+        let mass = mass_matrix(&bodies.0, multibody);
+        let free_mass = DMatrix::from_fn(free_dofs.len(), free_dofs.len(), |i, j| {
+            mass[(free_dofs[i], free_dofs[j])]
+        });
+        let free_impulses = DVector::from_fn(free_dofs.len(), |i, _| impulses[free_dofs[i]]);
+
+        let dvel = free_mass.lu().solve(&free_impulses).ok_or_else(|| {
+            JsError::new(
+                "The mass matrix of the multibody is singular. Note that it is only known after \
+                 the first timestep following the creation of the multibody.",
+            )
+        })?;
+        let mut vels = multibody.generalized_velocity_mut();
+        for (i, dof) in free_dofs.iter().enumerate() {
+            vels[*dof] += dvel[i];
+        }
+
+        if wakeUp {
+            for link in multibody.links() {
+                if let Some(rb) = bodies.0.get_mut(link.rigid_body_handle()) {
+                    rb.wake_up(true);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
-use crate::dynamics::{RawJointAxis, RawJointType, RawMultibodyJointSet};
+use crate::dynamics::{
+    RawJointAxis, RawJointType, RawMotorModel, RawMultibodyJointSet, RawRigidBodySet,
+};
 use crate::math::{RawRotation, RawVector};
-use crate::utils::FlatHandle;
-use rapier::dynamics::JointAxis;
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{JointAxis, MultibodyJoint};
+use rapier::math::{ANG_DIM, DIM};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -11,15 +14,22 @@ impl RawMultibodyJointSet {
         self.map(handle, |j| j.data.locked_axes.into())
     }
 
-    // /// The unique integer identifier of the first rigid-body this joint it attached to.
-    // pub fn jointBodyHandle1(&self, handle: FlatHandle) -> u32 {
-    //     self.map(handle, |j| j.body1.into_raw_parts().0)
-    // }
-    //
-    // /// The unique integer identifier of the second rigid-body this joint is attached to.
-    // pub fn jointBodyHandle2(&self, handle: FlatHandle) -> u32 {
-    //     self.map(handle, |j| j.body2.into_raw_parts().0)
-    // }
+    /// The unique integer identifier of the parent rigid-body of the link this joint is attached to.
+    ///
+    /// Returns `undefined` if this joint is attached to the root of its multibody.
+    pub fn jointBodyHandle1(&self, handle: FlatHandle) -> Option<FlatHandle> {
+        self.map_link(handle, |multibody, link| {
+            let parent = multibody.link(link.parent_id()?)?;
+            Some(utils::flat_handle(parent.rigid_body_handle().0))
+        })
+    }
+
+    /// The unique integer identifier of the rigid-body of the link this joint is attached to.
+    pub fn jointBodyHandle2(&self, handle: FlatHandle) -> FlatHandle {
+        self.map_link(handle, |_, link| {
+            utils::flat_handle(link.rigid_body_handle().0)
+        })
+    }
 
     /// The angular part of the joint’s local frame relative to the first rigid-body it is attached to.
     pub fn jointFrameX1(&self, handle: FlatHandle) -> RawRotation {
@@ -76,16 +86,45 @@ impl RawMultibodyJointSet {
         self.map(handle, |j| j.data.limits[axis as usize].max)
     }
 
-    // pub fn jointConfigureMotorModel(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     model: RawMotorModel,
-    // ) {
-    //     self.map_mut(handle, |j| {
-    //         j.data.motors[axis as usize].model = model.into()
-    //     })
-    // }
+    /// Sets the limits of this joint along the given axis.
+    ///
+    /// This wakes up the rigid-bodies of the multibody, like all the limit and motor setters.
+    pub fn jointSetLimits(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        min: f32,
+        max: f32,
+    ) {
+        self.map_mut_wake_up(bodies, handle, |j| {
+            j.data.set_limits(axis.into(), [min, max]);
+        });
+    }
+
+    pub fn jointConfigureMotorModel(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        model: RawMotorModel,
+    ) {
+        self.map_mut_wake_up(bodies, handle, |j| {
+            j.data.motors[axis as usize].model = model.into()
+        })
+    }
+
+    pub fn jointSetMotorMaxForce(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        maxForce: f32,
+    ) {
+        self.map_mut_wake_up(bodies, handle, |j| {
+            j.data.set_motor_max_force(axis.into(), maxForce);
+        })
+    }
 
     /*
     #[cfg(feature = "dim3")]
@@ -156,41 +195,92 @@ impl RawMultibodyJointSet {
     }
     */
 
-    // pub fn jointConfigureMotorVelocity(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     targetVel: f32,
-    //     factor: f32,
-    // ) {
-    //     self.jointConfigureMotor(handle, axis, 0.0, targetVel, 0.0, factor)
-    // }
-    //
-    // pub fn jointConfigureMotorPosition(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     targetPos: f32,
-    //     stiffness: f32,
-    //     damping: f32,
-    // ) {
-    //     self.jointConfigureMotor(handle, axis, targetPos, 0.0, stiffness, damping)
-    // }
-
-    // pub fn jointConfigureMotor(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     targetPos: f32,
-    //     targetVel: f32,
-    //     stiffness: f32,
-    //     damping: f32,
-    // ) {
-    //     self.map_mut(handle, |j| {
-    //         j.data.motors[axis as usize].target_pos = targetPos;
-    //         j.data.motors[axis as usize].target_vel = targetVel;
-    //         j.data.motors[axis as usize].stiffness = stiffness;
-    //         j.data.motors[axis as usize].damping = damping;
-    //     })
-    // }
+    pub fn jointConfigureMotorVelocity(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        targetVel: f32,
+        factor: f32,
+    ) {
+        self.jointConfigureMotor(bodies, handle, axis, 0.0, targetVel, 0.0, factor)
+    }
+
+    pub fn jointConfigureMotorPosition(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        targetPos: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.jointConfigureMotor(bodies, handle, axis, targetPos, 0.0, stiffness, damping)
+    }
+
+    pub fn jointConfigureMotor(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        targetPos: f32,
+        targetVel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.map_mut_wake_up(bodies, handle, |j| {
+            j.data
+                .set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
+        })
+    }
+
+    /// The number of degrees of freedom of this joint.
+    pub fn jointNdofs(&self, handle: FlatHandle) -> usize {
+        self.map(handle, |j| j.ndofs())
+    }
+
+    /// The coordinates of this joint, i.e., the position of the link it is attached to relative to
+    /// its parent, along each of the joint’s free axes.
+    ///
+    /// Contains one value per degree of freedom, with the free linear axes first (translations
+    /// along the joint’s local axes) followed by the free angular axes (components of the
+    /// rotation vector relative to the joint’s local frame, in radians).
+    pub fn jointCoords(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map(handle, |j| {
+            let mut coords = Vec::with_capacity(j.ndofs());
+            push_joint_coords(j, &mut coords);
+            coords
+        })
+    }
+
+    /// The velocities of this joint, i.e., the velocity of the link it is attached to relative to
+    /// its parent, along each of the joint’s free axes.
+    ///
+    /// Contains one value per degree of freedom, in the same order as `jointCoords`.
+    pub fn jointVelocities(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map_link(handle, |multibody, link| {
+            multibody.joint_velocity(link).iter().copied().collect()
+        })
+    }
+}
+
+/// Pushes to `out` the coordinates of the given joint along each of its free axes.
+///
+/// See `RawMultibodyJointSet::jointCoords` for the layout of the coordinates.
+pub(crate) fn push_joint_coords(joint: &MultibodyJoint, out: &mut Vec<f32>) {
+    let locked_bits = joint.data.locked_axes.bits();
+    let rel = joint.data.local_frame1.inverse() * joint.body_to_parent() * joint.data.local_frame2;
+    let angles = rel.rotation.scaled_axis();
+
+    for i in 0..DIM {
+        if locked_bits & (1 << i) == 0 {
+            out.push(rel.translation.vector[i]);
+        }
+    }
+
+    for i in 0..ANG_DIM {
+        if locked_bits & (1 << (DIM + i)) == 0 {
+            out.push(angles[i]);
+        }
+    }
 }
@@ -1,6 +1,12 @@
-use crate::dynamics::RawGenericJoint;
+use crate::dynamics::{RawGenericJoint, RawRigidBodySet};
+use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::{MultibodyJoint, MultibodyJointSet};
+use na::DVector;
+use rapier::dynamics::{
+    InverseKinematicsOption, JointAxesMask, Multibody, MultibodyJoint, MultibodyJointSet,
+    MultibodyLink,
+};
+use rapier::math::Isometry;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -15,6 +21,18 @@ impl RawMultibodyJointSet {
         f(body.link(link_id).unwrap().joint())
     }
 
+    pub(crate) fn map_link<T>(
+        &self,
+        handle: FlatHandle,
+        f: impl FnOnce(&Multibody, &MultibodyLink) -> T,
+    ) -> T {
+        let (body, link_id) = self
+            .0
+            .get(utils::multibody_joint_handle(handle))
+            .expect("Invalid Joint reference. It may have been removed from the physics World.");
+        f(body, body.link(link_id).unwrap())
+    }
+
     pub(crate) fn map_mut<T>(
         &mut self,
         handle: FlatHandle,
@@ -26,6 +44,29 @@ impl RawMultibodyJointSet {
             .expect("Invalid Joint reference. It may have been removed from the physics World.");
         f(&mut body.link_mut(link_id).unwrap().joint)
     }
+
+    /// Applies `f` to the given joint, then wakes up the rigid-bodies of its multibody so the
+    /// modification is taken into account even if the multibody was sleeping.
+    pub(crate) fn map_mut_wake_up<T>(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        f: impl FnOnce(&mut MultibodyJoint) -> T,
+    ) -> T {
+        let (multibody, link_id) = self
+            .0
+            .get_mut(utils::multibody_joint_handle(handle))
+            .expect("Invalid Joint reference. It may have been removed from the physics World.");
+        let result = f(&mut multibody.link_mut(link_id).unwrap().joint);
+
+        for link in multibody.links() {
+            if let Some(rb) = bodies.0.get_mut(link.rigid_body_handle()) {
+                rb.wake_up(true);
+            }
+        }
+
+        result
+    }
 }
 
 #[wasm_bindgen]
@@ -82,4 +123,73 @@ impl RawMultibodyJointSet {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// Computes the joint displacements needed to move the link a joint is attached to at the
+    /// given target pose, using the jacobian-based inverse-kinematics solver of its multibody.
+    ///
+    /// Returns the displacements in generalized coordinates, i.e., one value per degree of freedom
+    /// of the whole multibody the link is part of.
+    ///
+    /// # Parameters
+    /// - `handle`: the handle of the joint attached to the link to move.
+    /// - `targetTranslation`, `targetRotation`: the desired world-space pose of the link.
+    /// - `constrainedAxes`: the bit mask of the axes (as a `JointAxesMask`) the solver should
+    ///   solve for. The other axes of the link’s pose are left unconstrained.
+    /// - `damping`: the damping coefficient of the solver. Small values can lead to overshooting,
+    ///   large values can slow down convergence.
+    /// - `maxIters`: the maximum number of iterations of the solver.
+    /// - `epsilonLinear`, `epsilonAngular`: the solver stops iterating once both the linear and
+    ///   angular errors fall below these thresholds.
+    /// - `apply`: if `true`, the displacements are applied to the multibody, the poses of its
+    ///   rigid-bodies are updated accordingly, and these rigid-bodies are woken up.
+    pub fn inverseKinematics(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        targetTranslation: &RawVector,
+        targetRotation: &RawRotation,
+        constrainedAxes: u8,
+        damping: f32,
+        maxIters: usize,
+        epsilonLinear: f32,
+        epsilonAngular: f32,
+        apply: bool,
+    ) -> Vec<f32> {
+        let options = InverseKinematicsOption {
+            damping,
+            max_iters: maxIters,
+            constrained_axes: JointAxesMask::from_bits_truncate(constrainedAxes),
+            epsilon_linear: epsilonLinear,
+            epsilon_angular: epsilonAngular,
+        };
+        let target_pose = Isometry::from_parts(targetTranslation.0.into(), targetRotation.0);
+        let (multibody, link_id) = self
+            .0
+            .get_mut(utils::multibody_joint_handle(handle))
+            .expect("Invalid Joint reference. It may have been removed from the physics World.");
+        let mut displacements = DVector::zeros(multibody.ndofs());
+
+        multibody.inverse_kinematics(
+            &bodies.0,
+            link_id,
+            &options,
+            &target_pose,
+            |_| true,
+            &mut displacements,
+        );
+
+        if apply {
+            multibody.apply_displacements(displacements.as_slice());
+            multibody.forward_kinematics(&bodies.0, false);
+            multibody.update_rigid_bodies(&mut bodies.0, true);
+
+            for link in multibody.links() {
+                if let Some(rb) = bodies.0.get_mut(link.rigid_body_handle()) {
+                    rb.wake_up(true);
+                }
+            }
+        }
+
+        displacements.as_slice().to_vec()
+    }
 }
@@ -30,6 +30,52 @@ impl RawRigidBodySet {
         self.map(handle, |rb| rb.is_sleeping())
     }
 
+    /// The linear velocity below which this rigid-body can fall asleep, divided by the
+    /// `lengthUnit` of the integration parameters.
+    ///
+    /// A negative value means the rigid-body never falls asleep.
+    pub fn rbNormalizedLinearSleepThreshold(&self, handle: FlatHandle) -> f32 {
+        self.map(handle, |rb| rb.activation().normalized_linear_threshold)
+    }
+
+    /// Sets the linear velocity below which this rigid-body can fall asleep, divided by the
+    /// `lengthUnit` of the integration parameters.
+    ///
+    /// A negative value means the rigid-body never falls asleep.
+    pub fn rbSetNormalizedLinearSleepThreshold(&mut self, handle: FlatHandle, threshold: f32) {
+        self.map_mut(handle, |rb| {
+            rb.activation_mut().normalized_linear_threshold = threshold
+        })
+    }
+
+    /// The angular velocity below which this rigid-body can fall asleep.
+    ///
+    /// A negative value means the rigid-body never falls asleep.
+    pub fn rbAngularSleepThreshold(&self, handle: FlatHandle) -> f32 {
+        self.map(handle, |rb| rb.activation().angular_threshold)
+    }
+
+    /// Sets the angular velocity below which this rigid-body can fall asleep.
+    ///
+    /// A negative value means the rigid-body never falls asleep.
+    pub fn rbSetAngularSleepThreshold(&mut self, handle: FlatHandle, threshold: f32) {
+        self.map_mut(handle, |rb| {
+            rb.activation_mut().angular_threshold = threshold
+        })
+    }
+
+    /// The time, in seconds, this rigid-body must remain below its sleep thresholds before
+    /// falling asleep.
+    pub fn rbTimeUntilSleep(&self, handle: FlatHandle) -> f32 {
+        self.map(handle, |rb| rb.activation().time_until_sleep)
+    }
+
+    /// Sets the time, in seconds, this rigid-body must remain below its sleep thresholds before
+    /// falling asleep.
+    pub fn rbSetTimeUntilSleep(&mut self, handle: FlatHandle, time: f32) {
+        self.map_mut(handle, |rb| rb.activation_mut().time_until_sleep = time)
+    }
+
     /// Is the velocity of this rigid-body not zero?
     pub fn rbIsMoving(&self, handle: FlatHandle) -> bool {
         self.map(handle, |rb| rb.is_moving())
@@ -0,0 +1,292 @@
+use crate::dynamics::{RawIslandManager, RawRigidBodySet};
+use crate::math::ROTATION_DIM;
+use crate::utils;
+use js_sys::Float32Array;
+use rapier::dynamics::{RigidBody, RigidBodyHandle};
+use rapier::math::{Vector, ANG_DIM, DIM};
+use wasm_bindgen::prelude::*;
+
+/// Number of floats used to encode the pose of one rigid-body in a `RawRigidBodyPoses` buffer:
+/// `[handle (2 floats), tx, ty, tz, qx, qy, qz, qw, padding]`.
+#[cfg(feature = "dim3")]
+const POSE_STRIDE: usize = 10;
+/// Number of floats used to encode the pose of one rigid-body in a `RawRigidBodyPoses` buffer:
+/// `[handle (2 floats), tx, ty, angle, padding]`.
+#[cfg(feature = "dim2")]
+const POSE_STRIDE: usize = 6;
+
+/// A packed buffer of rigid-body poses, filled by `RawRigidBodySet.exportPoses`.
+///
+/// Each rigid-body occupies `RawRigidBodyPoses.stride()` consecutive floats. The first two floats
+/// of each entry are the bits of the rigid-body’s handle: reading the buffer through a
+/// `Float64Array` view gives the handle back at index `i * stride / 2`.
+#[wasm_bindgen]
+pub struct RawRigidBodyPoses {
+    // NOTE: stored as `f64` so the buffer is always 8-bytes aligned. This is what
+    //       allows JS to read the handles through a `Float64Array` view.
+    data: Vec<f64>,
+    len: usize,
+}
+
+impl RawRigidBodyPoses {
+    fn as_f32_slice(&self) -> &[f32] {
+        // SAFETY: `f32` has a smaller alignment than `f64`, and any bit pattern is a valid `f32`.
+        unsafe {
+            std::slice::from_raw_parts(self.data.as_ptr() as *const f32, self.len * POSE_STRIDE)
+        }
+    }
+
+    fn as_f32_slice_mut(&mut self) -> &mut [f32] {
+        // SAFETY: `f32` has a smaller alignment than `f64`, and any bit pattern is a valid `f32`.
+        unsafe {
+            std::slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut f32, self.data.len() * 2)
+        }
+    }
+
+    fn fill<'a>(
+        &mut self,
+        capacity: usize,
+        bodies: impl Iterator<Item = (RigidBodyHandle, &'a RigidBody)>,
+    ) {
+        self.data.clear();
+        self.data.resize(capacity * POSE_STRIDE / 2, 0.0);
+
+        let buf = self.as_f32_slice_mut();
+        let mut len = 0;
+
+        for (entry, (handle, rb)) in buf.chunks_exact_mut(POSE_STRIDE).zip(bodies) {
+            let handle_bits = utils::flat_handle(handle.0).to_bits();
+            let pos = rb.position();
+            entry[0] = f32::from_bits(handle_bits as u32);
+            entry[1] = f32::from_bits((handle_bits >> 32) as u32);
+
+            #[cfg(feature = "dim2")]
+            {
+                entry[2] = pos.translation.x;
+                entry[3] = pos.translation.y;
+                entry[4] = pos.rotation.angle();
+            }
+            #[cfg(feature = "dim3")]
+            {
+                entry[2] = pos.translation.x;
+                entry[3] = pos.translation.y;
+                entry[4] = pos.translation.z;
+                entry[5] = pos.rotation.i;
+                entry[6] = pos.rotation.j;
+                entry[7] = pos.rotation.k;
+                entry[8] = pos.rotation.w;
+            }
+
+            len += 1;
+        }
+
+        self.len = len;
+    }
+}
+
+impl Default for RawRigidBodyPoses {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl RawRigidBodyPoses {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            data: vec![],
+            len: 0,
+        }
+    }
+
+    /// The number of floats used to encode the pose of a single rigid-body.
+    pub fn stride() -> usize {
+        POSE_STRIDE
+    }
+
+    /// The number of rigid-body poses contained in this buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Does this buffer contain no rigid-body pose?
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// A copy of this buffer.
+    pub fn data(&self) -> Float32Array {
+        let data = self.as_f32_slice();
+        let output = Float32Array::new_with_length(data.len() as u32);
+        output.copy_from(data);
+        output
+    }
+
+    /// A view of this buffer directly into the WASM memory, without any copy.
+    ///
+    /// The returned array is invalidated by any subsequent call to `exportPoses` with this buffer,
+    /// by freeing this buffer, and by any growth of the WASM memory (which can happen as part of
+    /// any other call to the physics engine). It must not be kept around.
+    pub fn view(&self) -> Float32Array {
+        // SAFETY: see the doc-comment above. We can’t enforce the validity of the view
+        //         from Rust, so the TypeScript wrapper is responsible for not storing it.
+        unsafe { Float32Array::view(self.as_f32_slice()) }
+    }
+}
+
+#[wasm_bindgen]
+impl RawRigidBodySet {
+    /// Writes the handle, translation, and rotation of every rigid-body of this set into `out`.
+    pub fn exportPoses(&self, out: &mut RawRigidBodyPoses) {
+        out.fill(self.0.len(), self.0.iter());
+    }
+
+    /// Writes the handle, translation, and rotation of every active (non-sleeping) rigid-body
+    /// into `out`.
+    pub fn exportActivePoses(&self, islands: &RawIslandManager, out: &mut RawRigidBodyPoses) {
+        let active_bodies = islands.0.active_bodies();
+        out.fill(
+            active_bodies.len(),
+            active_bodies
+                .iter()
+                .filter_map(|handle| Some((*handle, self.0.get(*handle)?))),
+        );
+    }
+}
+
+/// Checks that `values` contains exactly `stride` floats per rigid-body handle.
+fn check_packed_len(
+    handles: &[f64],
+    values: &[f32],
+    stride: usize,
+    name: &str,
+) -> Result<(), JsError> {
+    if values.len() != handles.len() * stride {
+        return Err(JsError::new(&format!(
+            "Expected {} floats in `{}` ({} per rigid-body), got {}.",
+            handles.len() * stride,
+            name,
+            stride,
+            values.len()
+        )));
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen]
+impl RawRigidBodySet {
+    /// Sets the next kinematic translations of several rigid-bodies at once.
+    ///
+    /// This is equivalent to calling `rbSetNextKinematicTranslation` on each rigid-body.
+    ///
+    /// # Parameters
+    /// - `handles`: the handles of the rigid-bodies to modify.
+    /// - `translations`: the packed world-space translations, with two (in 2D) or three (in 3D)
+    ///   consecutive floats per rigid-body, in the same order as `handles`.
+    pub fn rbSetNextKinematicTranslations(
+        &mut self,
+        handles: Vec<f64>,
+        translations: Vec<f32>,
+    ) -> Result<(), JsError> {
+        check_packed_len(&handles, &translations, DIM, "translations")?;
+
+        for (handle, tra) in handles.iter().zip(translations.chunks_exact(DIM)) {
+            self.map_mut(*handle, |rb| {
+                rb.set_next_kinematic_translation(Vector::from_column_slice(tra));
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets the next kinematic rotations of several rigid-bodies at once.
+    ///
+    /// This is equivalent to calling `rbSetNextKinematicRotation` on each rigid-body. Zero
+    /// quaternions are ignored.
+    ///
+    /// # Parameters
+    /// - `handles`: the handles of the rigid-bodies to modify.
+    /// - `rotations`: the packed world-space rotations, with one angle (in 2D) or four consecutive
+    ///   quaternion components `x, y, z, w` (in 3D) per rigid-body, in the same order as `handles`.
+    pub fn rbSetNextKinematicRotations(
+        &mut self,
+        handles: Vec<f64>,
+        rotations: Vec<f32>,
+    ) -> Result<(), JsError> {
+        check_packed_len(&handles, &rotations, ROTATION_DIM, "rotations")?;
+
+        for (handle, rot) in handles.iter().zip(rotations.chunks_exact(ROTATION_DIM)) {
+            #[cfg(feature = "dim2")]
+            let rot = Some(na::UnitComplex::new(rot[0]));
+            #[cfg(feature = "dim3")]
+            let rot = na::Unit::try_new(na::Quaternion::new(rot[3], rot[0], rot[1], rot[2]), 0.0);
+
+            if let Some(rot) = rot {
+                self.map_mut(*handle, |rb| rb.set_next_kinematic_rotation(rot));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the linear velocities of several rigid-bodies at once.
+    ///
+    /// This is equivalent to calling `rbSetLinvel` on each rigid-body.
+    ///
+    /// # Parameters
+    /// - `handles`: the handles of the rigid-bodies to modify.
+    /// - `linvels`: the packed linear velocities, with two (in 2D) or three (in 3D) consecutive
+    ///   floats per rigid-body, in the same order as `handles`.
+    /// - `wakeUp`: forces the rigid-bodies to wake-up.
+    pub fn rbSetLinvels(
+        &mut self,
+        handles: Vec<f64>,
+        linvels: Vec<f32>,
+        wakeUp: bool,
+    ) -> Result<(), JsError> {
+        check_packed_len(&handles, &linvels, DIM, "linvels")?;
+
+        for (handle, linvel) in handles.iter().zip(linvels.chunks_exact(DIM)) {
+            self.map_mut(*handle, |rb| {
+                rb.set_linvel(Vector::from_column_slice(linvel), wakeUp);
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets the angular velocities of several rigid-bodies at once.
+    ///
+    /// This is equivalent to calling `rbSetAngvel` on each rigid-body.
+    ///
+    /// # Parameters
+    /// - `handles`: the handles of the rigid-bodies to modify.
+    /// - `angvels`: the packed angular velocities, with one (in 2D) or three (in 3D) consecutive
+    ///   floats per rigid-body, in the same order as `handles`.
+    /// - `wakeUp`: forces the rigid-bodies to wake-up.
+    pub fn rbSetAngvels(
+        &mut self,
+        handles: Vec<f64>,
+        angvels: Vec<f32>,
+        wakeUp: bool,
+    ) -> Result<(), JsError> {
+        check_packed_len(&handles, &angvels, ANG_DIM, "angvels")?;
+
+        #[cfg(feature = "dim2")]
+        for (handle, angvel) in handles.iter().zip(angvels.iter()) {
+            self.map_mut(*handle, |rb| rb.set_angvel(*angvel, wakeUp));
+        }
+
+        #[cfg(feature = "dim3")]
+        for (handle, angvel) in handles.iter().zip(angvels.chunks_exact(3)) {
+            self.map_mut(*handle, |rb| {
+                rb.set_angvel(Vector::from_column_slice(angvel), wakeUp);
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -35,6 +35,25 @@ impl Into<RawRigidBodyType> for RigidBodyType {
     }
 }
 
+/// Sets the activation thresholds of a rigid-body built by a `RigidBodyBuilder`.
+///
+/// The velocity thresholds are left untouched if the rigid-body can’t sleep, since
+/// `RigidBodyBuilder::can_sleep(false)` disables sleeping by setting them to negative values.
+pub(crate) fn set_activation_thresholds(
+    rb: &mut RigidBody,
+    can_sleep: bool,
+    normalized_linear_threshold: f32,
+    angular_threshold: f32,
+    time_until_sleep: f32,
+) {
+    let activation = rb.activation_mut();
+    if can_sleep {
+        activation.normalized_linear_threshold = normalized_linear_threshold;
+        activation.angular_threshold = angular_threshold;
+    }
+    activation.time_until_sleep = time_until_sleep;
+}
+
 #[wasm_bindgen]
 pub struct RawRigidBodySet(pub(crate) RigidBodySet);
 
@@ -90,6 +109,9 @@ impl RawRigidBodySet {
         rb_type: RawRigidBodyType,
         canSleep: bool,
         sleeping: bool,
+        normalizedLinearSleepThreshold: f32,
+        angularSleepThreshold: f32,
+        timeUntilSleep: f32,
         softCcdPrediction: f32,
         ccdEnabled: bool,
         dominanceGroup: i8,
@@ -130,7 +152,16 @@ impl RawRigidBodySet {
             rigid_body.additional_mass_properties(props)
         };
 
-        utils::flat_handle(self.0.insert(rigid_body.build()).0)
+        let mut rigid_body = rigid_body.build();
+        set_activation_thresholds(
+            &mut rigid_body,
+            canSleep,
+            normalizedLinearSleepThreshold,
+            angularSleepThreshold,
+            timeUntilSleep,
+        );
+
+        utils::flat_handle(self.0.insert(rigid_body).0)
     }
 
     #[cfg(feature = "dim2")]
@@ -154,6 +185,9 @@ impl RawRigidBodySet {
         rb_type: RawRigidBodyType,
         canSleep: bool,
         sleeping: bool,
+        normalizedLinearSleepThreshold: f32,
+        angularSleepThreshold: f32,
+        timeUntilSleep: f32,
         softCcdPrediciton: f32,
         ccdEnabled: bool,
         dominanceGroup: i8,
@@ -187,7 +221,16 @@ impl RawRigidBodySet {
             rigid_body = rigid_body.lock_rotations();
         }
 
-        utils::flat_handle(self.0.insert(rigid_body.build()).0)
+        let mut rigid_body = rigid_body.build();
+        set_activation_thresholds(
+            &mut rigid_body,
+            canSleep,
+            normalizedLinearSleepThreshold,
+            angularSleepThreshold,
+            timeUntilSleep,
+        );
+
+        utils::flat_handle(self.0.insert(rigid_body).0)
     }
 
     pub fn remove(
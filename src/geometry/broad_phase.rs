@@ -1,18 +1,56 @@
 use crate::dynamics::RawRigidBodySet;
+use crate::geometry::feature::IntoTypeValue;
 use crate::geometry::{
-    RawColliderSet, RawColliderShapeCastHit, RawNarrowPhase, RawPointColliderProjection,
-    RawRayColliderHit, RawRayColliderIntersection, RawShape,
+    RawColliderSet, RawColliderShapeCastHit, RawFeatureType, RawNarrowPhase,
+    RawPointColliderProjection, RawRayColliderHit, RawRayColliderIntersection, RawShape,
 };
-use crate::math::{RawRotation, RawVector};
+use crate::math::{RawRotation, RawVector, ROTATION_DIM};
 use crate::utils::{self, FlatHandle};
 use rapier::geometry::DefaultBroadPhase;
 use rapier::geometry::{Aabb, ColliderHandle, Ray};
-use rapier::math::{Isometry, Point};
+use rapier::math::{Isometry, Point, Vector, DIM};
 use rapier::parry::query::ShapeCastOptions;
 use rapier::pipeline::{QueryFilter, QueryFilterFlags};
 use rapier::prelude::FeatureId;
 use wasm_bindgen::prelude::*;
 
+/// Number of floats used to encode the result of one ray by `RawBroadPhase::castRays`:
+/// `[collider, time_of_impact, normal (DIM floats), feature_type, feature_id]`.
+const RAY_HIT_STRIDE: usize = 4 + DIM;
+/// Number of floats used to encode the result of one shape-cast by `RawBroadPhase::castShapes`:
+/// `[collider, time_of_impact, witness1, witness2, normal1, normal2 (DIM floats each)]`.
+const SHAPE_CAST_HIT_STRIDE: usize = 2 + 4 * DIM;
+
+/// Reads the poses packed into `translations` and `rotations`, checking that both contain
+/// the same number of poses and that all the rotations are valid.
+fn packed_poses(translations: &[f32], rotations: &[f32]) -> Result<Vec<Isometry<f32>>, JsError> {
+    let num_poses = translations.len() / DIM;
+    if translations.len() != num_poses * DIM || rotations.len() != num_poses * ROTATION_DIM {
+        return Err(JsError::new(
+            "The shape translations and rotations must contain the same number of poses.",
+        ));
+    }
+
+    translations
+        .chunks_exact(DIM)
+        .zip(rotations.chunks_exact(ROTATION_DIM))
+        .enumerate()
+        .map(|(i, (tra, rot))| {
+            #[cfg(feature = "dim2")]
+            let rot = Some(na::UnitComplex::new(rot[0]));
+            #[cfg(feature = "dim3")]
+            let rot = na::Unit::try_new(na::Quaternion::new(rot[3], rot[0], rot[1], rot[2]), 0.0);
+            let rot = rot.ok_or_else(|| {
+                JsError::new(&format!("The shape rotation {} is a zero quaternion.", i))
+            })?;
+            Ok(Isometry::from_parts(
+                Vector::from_column_slice(tra).into(),
+                rot,
+            ))
+        })
+        .collect()
+}
+
 #[wasm_bindgen]
 pub struct RawBroadPhase(pub(crate) DefaultBroadPhase);
 
@@ -104,6 +142,94 @@ impl RawBroadPhase {
         Some(RawRayColliderIntersection { handle, inter })
     }
 
+    /// The number of floats used to encode the result of one ray in the array returned by
+    /// `castRays`.
+    pub fn rayHitStride() -> usize {
+        RAY_HIT_STRIDE
+    }
+
+    /// Casts several rays at once, all with the same filter, and returns their closest
+    /// intersection packed into a single array.
+    ///
+    /// The i-th ray starts at the i-th point of `rayOrigs`, has the i-th direction of `rayDirs`,
+    /// and the i-th max time-of-impact of `maxTois`. The result of each ray occupies
+    /// `rayHitStride()` consecutive elements: `[collider, timeOfImpact, normal (2 or 3 elements),
+    /// featureType, featureId]`. If a ray doesn’t hit anything, `collider`, `timeOfImpact`, and
+    /// `featureId` are set to `-1`, and `featureType` to `Unknown`. If the feature hit has no id,
+    /// `featureId` is set to `-1`.
+    pub fn castRays(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        rayOrigs: &[f32],
+        rayDirs: &[f32],
+        maxTois: &[f32],
+        solid: bool,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+    ) -> Result<Vec<f64>, JsError> {
+        let num_rays = maxTois.len();
+        if rayOrigs.len() != num_rays * DIM || rayDirs.len() != num_rays * DIM {
+            return Err(JsError::new(
+                "The ray origins and directions must contain exactly one vector per max time-of-impact.",
+            ));
+        }
+
+        let mut result = vec![0.0; num_rays * RAY_HIT_STRIDE];
+
+        utils::with_filter(filter_predicate, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let rays = rayOrigs
+                .chunks_exact(DIM)
+                .zip(rayDirs.chunks_exact(DIM))
+                .zip(maxTois);
+
+            for (entry, ((orig, dir), max_toi)) in result.chunks_exact_mut(RAY_HIT_STRIDE).zip(rays)
+            {
+                let ray = Ray::new(Point::from_slice(orig), Vector::from_row_slice(dir));
+
+                match query_pipeline.cast_ray_and_get_normal(&ray, *max_toi, solid) {
+                    Some((handle, inter)) => {
+                        entry[0] = utils::flat_handle(handle.0);
+                        entry[1] = inter.time_of_impact as f64;
+                        for (out, n) in entry[2..2 + DIM].iter_mut().zip(inter.normal.iter()) {
+                            *out = *n as f64;
+                        }
+                        entry[2 + DIM] = inter.feature.into_type() as u32 as f64;
+                        entry[3 + DIM] = inter.feature.into_value().map_or(-1.0, |id| id as f64);
+                    }
+                    None => {
+                        entry[0] = -1.0;
+                        entry[1] = -1.0;
+                        entry[2 + DIM] = RawFeatureType::Unknown as u32 as f64;
+                        entry[3 + DIM] = -1.0;
+                    }
+                }
+            }
+        });
+
+        Ok(result)
+    }
+
     // The callback is of type (RawRayColliderIntersection) => bool
     pub fn intersectionsWithRay(
         &self,
@@ -198,6 +324,58 @@ impl RawBroadPhase {
         })
     }
 
+    /// Tests the same shape at several poses at once, all with the same filter, and returns the
+    /// first collider found intersecting the shape at each pose.
+    ///
+    /// The i-th pose is given by the i-th translation of `shapePoss` and rotation of `shapeRots`
+    /// (one angle in 2D, or four quaternion components `x, y, z, w` in 3D). The i-th element
+    /// of the result is the handle of the collider intersecting the shape at the i-th pose, or
+    /// `-1` if there is none.
+    pub fn intersectionWithShapes(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePoss: &[f32],
+        shapeRots: &[f32],
+        shape: &RawShape,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+    ) -> Result<Vec<f64>, JsError> {
+        let poses = packed_poses(shapePoss, shapeRots)?;
+
+        Ok(utils::with_filter(filter_predicate, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            poses
+                .into_iter()
+                .map(|pos| {
+                    query_pipeline
+                        .intersect_shape(pos, &*shape.0)
+                        .next()
+                        .map_or(-1.0, |(handle, _)| utils::flat_handle(handle.0))
+                })
+                .collect()
+        }))
+    }
+
     pub fn projectPoint(
         &self,
         narrow_phase: &RawNarrowPhase,
@@ -376,6 +554,102 @@ impl RawBroadPhase {
         })
     }
 
+    /// The number of floats used to encode the result of one shape-cast in the array returned
+    /// by `castShapes`.
+    pub fn shapeCastHitStride() -> usize {
+        SHAPE_CAST_HIT_STRIDE
+    }
+
+    /// Casts the same shape from several poses at once, all with the same filter, and returns
+    /// their first hit packed into a single array.
+    ///
+    /// The i-th cast starts at the i-th translation of `shapePoss` and rotation of `shapeRots`
+    /// (one angle in 2D, or four quaternion components `x, y, z, w` in 3D), and moves with the
+    /// i-th velocity of `shapeVels`. The result of each cast occupies `shapeCastHitStride()`
+    /// consecutive elements: `[collider, timeOfImpact, witness1, witness2, normal1, normal2]`,
+    /// with 2 or 3 elements per vector. If a cast doesn’t hit anything, `collider` and
+    /// `timeOfImpact` are set to `-1`.
+    pub fn castShapes(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePoss: &[f32],
+        shapeRots: &[f32],
+        shapeVels: &[f32],
+        shape: &RawShape,
+        target_distance: f32,
+        maxToi: f32,
+        stop_at_penetration: bool,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+    ) -> Result<Vec<f64>, JsError> {
+        let num_casts = shapePoss.len() / DIM;
+        if shapeVels.len() != shapePoss.len() {
+            return Err(JsError::new(
+                "The shape velocities must contain exactly one vector per shape pose.",
+            ));
+        }
+
+        let poses = packed_poses(shapePoss, shapeRots)?;
+        let mut result = vec![0.0; num_casts * SHAPE_CAST_HIT_STRIDE];
+
+        utils::with_filter(filter_predicate, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let options = ShapeCastOptions {
+                max_time_of_impact: maxToi,
+                stop_at_penetration,
+                compute_impact_geometry_on_penetration: true,
+                target_distance,
+            };
+            let casts = poses.iter().zip(shapeVels.chunks_exact(DIM));
+
+            for (entry, (pos, vel)) in result.chunks_exact_mut(SHAPE_CAST_HIT_STRIDE).zip(casts) {
+                let vel = Vector::from_column_slice(vel);
+
+                match query_pipeline.cast_shape(pos, &vel, &*shape.0, options) {
+                    Some((handle, hit)) => {
+                        entry[0] = utils::flat_handle(handle.0);
+                        entry[1] = hit.time_of_impact as f64;
+                        let vectors = hit
+                            .witness1
+                            .iter()
+                            .chain(hit.witness2.iter())
+                            .chain(hit.normal1.iter())
+                            .chain(hit.normal2.iter());
+                        for (out, val) in entry[2..].iter_mut().zip(vectors) {
+                            *out = *val as f64;
+                        }
+                    }
+                    None => {
+                        entry[0] = -1.0;
+                        entry[1] = -1.0;
+                    }
+                }
+            }
+        });
+
+        Ok(result)
+    }
+
     // The callback has type (u32) => boolean
     pub fn intersectionsWithShape(
         &self,
@@ -11,6 +11,15 @@ use rapier::math::{Point, Rotation, Vector};
 use rapier::parry::utils::SdpMatrix3;
 use wasm_bindgen::prelude::*;
 
+/// Number of floats used to encode one rotation in packed arrays: an angle in 2D, and the
+/// quaternion components `x, y, z, w` in 3D.
+#[cfg(feature = "dim2")]
+pub(crate) const ROTATION_DIM: usize = 1;
+/// Number of floats used to encode one rotation in packed arrays: an angle in 2D, and the
+/// quaternion components `x, y, z, w` in 3D.
+#[cfg(feature = "dim3")]
+pub(crate) const ROTATION_DIM: usize = 4;
+
 #[wasm_bindgen]
 #[repr(transparent)]
 #[derive(Copy, Clone)]
@@ -1,21 +1,59 @@
 use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
 use crate::geometry::{RawColliderSet, RawNarrowPhase};
-use js_sys::Float32Array;
+use crate::utils::{self, FlatHandle};
+use js_sys::{Float32Array, Float64Array, Uint32Array};
 use palette::convert::IntoColorUnclamped;
 use palette::rgb::Rgba;
 use palette::Hsla;
 use rapier::dynamics::{RigidBody, RigidBodySet};
-use rapier::geometry::ColliderSet;
-use rapier::math::{Point, Real};
-use rapier::pipeline::{DebugRenderBackend, DebugRenderObject, DebugRenderPipeline};
+use rapier::geometry::{ColliderSet, Shape, ShapeType};
+#[cfg(feature = "dim2")]
+use rapier::geometry::{Cuboid, HeightField, Voxels};
+#[cfg(feature = "dim2")]
+use rapier::math::Vector;
+use rapier::math::{Isometry, Point, Real, DIM};
+use rapier::pipeline::{
+    DebugColor, DebugRenderBackend, DebugRenderMode, DebugRenderObject, DebugRenderPipeline,
+    DebugRenderStyle,
+};
 use rapier::prelude::{QueryFilter, QueryFilterFlags};
 use wasm_bindgen::prelude::*;
 
+/// Number of floats used to encode the object a debug-render line comes from:
+/// `[object_type, handle1, handle2]`.
+const DEBUG_RENDER_OBJECT_STRIDE: usize = 3;
+
+/// Number of floats used to encode the triangle mesh of one collider:
+/// `[collider, first_index, num_indices]`.
+const TRIANGLE_MESH_STRIDE: usize = 3;
+
+/// A `DebugRenderMode` flag, handled by these bindings, indicating that the triangle meshes
+/// approximating the collider shapes must be rendered.
+const COLLIDER_TRIANGLES: u32 = 1 << 7;
+
+/// The kind of object a debug-render line was generated from.
+#[wasm_bindgen]
+#[derive(Copy, Clone)]
+pub enum RawDebugRenderObjectType {
+    RigidBody,
+    Collider,
+    ColliderAabb,
+    ImpulseJoint,
+    MultibodyJoint,
+    ContactPair,
+}
+
 #[wasm_bindgen]
 pub struct RawDebugRenderPipeline {
     pub(crate) raw: DebugRenderPipeline,
     vertices: Vec<f32>,
     colors: Vec<f32>,
+    objects: Vec<f64>,
+    record_objects: bool,
+    triangle_vertices: Vec<f32>,
+    triangle_indices: Vec<u32>,
+    triangle_meshes: Vec<f64>,
+    render_triangles: bool,
 }
 
 #[wasm_bindgen]
@@ -26,6 +64,45 @@ impl RawDebugRenderPipeline {
             raw: DebugRenderPipeline::default(),
             vertices: vec![],
             colors: vec![],
+            objects: vec![],
+            record_objects: false,
+            triangle_vertices: vec![],
+            triangle_indices: vec![],
+            triangle_meshes: vec![],
+            render_triangles: false,
+        }
+    }
+
+    /// The flags indicating what part of the physics engine is rendered.
+    pub fn mode(&self) -> u32 {
+        if self.render_triangles {
+            self.raw.mode.bits() | COLLIDER_TRIANGLES
+        } else {
+            self.raw.mode.bits()
+        }
+    }
+
+    pub fn setMode(&mut self, mode: u32) {
+        self.raw.mode = DebugRenderMode::from_bits_truncate(mode);
+        self.render_triangles = mode & COLLIDER_TRIANGLES != 0;
+        if !self.render_triangles {
+            self.triangle_vertices = vec![];
+            self.triangle_indices = vec![];
+            self.triangle_meshes = vec![];
+        }
+    }
+
+    /// A copy of the style used for computing the line colors.
+    pub fn style(&self) -> RawDebugRenderStyle {
+        RawDebugRenderStyle(self.raw.style)
+    }
+
+    pub fn setStyle(&mut self, style: &RawDebugRenderStyle) {
+        if style.0.subdivisions != self.raw.style.subdivisions {
+            // The shape outlines are precomputed from the number of subdivisions.
+            self.raw = DebugRenderPipeline::new(style.0, self.raw.mode);
+        } else {
+            self.raw.style = style.0;
         }
     }
 
@@ -41,6 +118,70 @@ impl RawDebugRenderPipeline {
         output
     }
 
+    /// Is the object each line comes from recorded by `render`?
+    pub fn recordObjects(&self) -> bool {
+        self.record_objects
+    }
+
+    pub fn setRecordObjects(&mut self, enabled: bool) {
+        self.record_objects = enabled;
+        if !enabled {
+            self.objects = vec![];
+        }
+    }
+
+    /// The number of floats used to encode the object of one line in the array returned by
+    /// `objects`.
+    pub fn objectStride() -> usize {
+        DEBUG_RENDER_OBJECT_STRIDE
+    }
+
+    /// The objects the rendered lines come from, if `recordObjects` is enabled.
+    ///
+    /// Each line is described by `objectStride()` consecutive elements:
+    /// `[objectType, handle1, handle2]` where `objectType` is a `RawDebugRenderObjectType`,
+    /// and `handle1` is the handle of the rigid-body, collider, or joint the line comes from.
+    /// For contact pairs, `handle1` and `handle2` are the handles of the two colliders in
+    /// contact. Otherwise `handle2` is `-1`.
+    pub fn objects(&self) -> Float64Array {
+        let output = Float64Array::new_with_length(self.objects.len() as u32);
+        output.copy_from(&self.objects);
+        output
+    }
+
+    /// The vertices of the triangle meshes rendered for the colliders, in world-space, if the
+    /// `COLLIDER_TRIANGLES` mode flag is set.
+    pub fn triangleVertices(&self) -> Float32Array {
+        let output = Float32Array::new_with_length(self.triangle_vertices.len() as u32);
+        output.copy_from(&self.triangle_vertices);
+        output
+    }
+
+    /// The indices of the triangle meshes rendered for the colliders, with three indices into
+    /// `triangleVertices` per triangle.
+    pub fn triangleIndices(&self) -> Uint32Array {
+        let output = Uint32Array::new_with_length(self.triangle_indices.len() as u32);
+        output.copy_from(&self.triangle_indices);
+        output
+    }
+
+    /// The number of floats used to encode one collider in the array returned by
+    /// `triangleMeshes`.
+    pub fn triangleMeshStride() -> usize {
+        TRIANGLE_MESH_STRIDE
+    }
+
+    /// The colliders the triangle meshes were rendered for.
+    ///
+    /// Each collider is described by `triangleMeshStride()` consecutive elements:
+    /// `[collider, firstIndex, numIndices]`, where `firstIndex` and `numIndices` delimit the
+    /// part of `triangleIndices` describing the triangles of this collider.
+    pub fn triangleMeshes(&self) -> Float64Array {
+        let output = Float64Array::new_with_length(self.triangle_meshes.len() as u32);
+        output.copy_from(&self.triangle_meshes);
+        output
+    }
+
     pub fn render(
         &mut self,
         bodies: &RawRigidBodySet,
@@ -49,25 +190,37 @@ impl RawDebugRenderPipeline {
         multibody_joints: &RawMultibodyJointSet,
         narrow_phase: &RawNarrowPhase,
         filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) {
         self.vertices.clear();
         self.colors.clear();
+        self.objects.clear();
+        self.triangle_vertices.clear();
+        self.triangle_indices.clear();
+        self.triangle_meshes.clear();
 
         crate::utils::with_filter(filter_predicate, |predicate| {
             let mut backend = CopyToBuffersBackend {
                 filter: QueryFilter {
                     flags: QueryFilterFlags::from_bits(filter_flags)
                         .unwrap_or(QueryFilterFlags::empty()),
-                    groups: None,
-                    exclude_collider: None,
-                    exclude_rigid_body: None,
+                    groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                    exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                    exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
                     predicate,
                 },
                 bodies: &bodies.0,
                 colliders: &colliders.0,
                 vertices: &mut self.vertices,
                 colors: &mut self.colors,
+                objects: if self.record_objects {
+                    Some(&mut self.objects)
+                } else {
+                    None
+                },
             };
 
             self.raw.render(
@@ -77,17 +230,385 @@ impl RawDebugRenderPipeline {
                 &impulse_joints.0,
                 &multibody_joints.0,
                 &narrow_phase.0,
-            )
+            );
+
+            if self.render_triangles {
+                for (handle, co) in colliders.0.iter() {
+                    if !backend.filter_object(DebugRenderObject::Collider(handle, co)) {
+                        continue;
+                    }
+
+                    let first_index = self.triangle_indices.len();
+                    push_shape_triangles(
+                        co.shape(),
+                        co.position(),
+                        self.raw.style.subdivisions,
+                        &mut self.triangle_vertices,
+                        &mut self.triangle_indices,
+                    );
+                    let num_indices = self.triangle_indices.len() - first_index;
+
+                    if num_indices != 0 {
+                        self.triangle_meshes.extend_from_slice(&[
+                            utils::flat_handle(handle.0),
+                            first_index as f64,
+                            num_indices as f64,
+                        ]);
+                    }
+                }
+            }
         })
     }
 }
 
+/// The style used for computing the line colors of the debug-render pipeline.
+///
+/// All the colors are given in HSLA (Hue Saturation Lightness Alpha) format.
+#[wasm_bindgen]
+pub struct RawDebugRenderStyle(pub(crate) DebugRenderStyle);
+
+impl Default for RawDebugRenderStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl RawDebugRenderStyle {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        RawDebugRenderStyle(DebugRenderStyle::default())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn subdivisions(&self) -> u32 {
+        self.0.subdivisions
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn borderSubdivisions(&self) -> u32 {
+        self.0.border_subdivisions
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn colliderDynamicColor(&self) -> Vec<f32> {
+        self.0.collider_dynamic_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn colliderFixedColor(&self) -> Vec<f32> {
+        self.0.collider_fixed_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn colliderKinematicColor(&self) -> Vec<f32> {
+        self.0.collider_kinematic_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn colliderParentlessColor(&self) -> Vec<f32> {
+        self.0.collider_parentless_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn impulseJointAnchorColor(&self) -> Vec<f32> {
+        self.0.impulse_joint_anchor_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn impulseJointSeparationColor(&self) -> Vec<f32> {
+        self.0.impulse_joint_separation_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn multibodyJointAnchorColor(&self) -> Vec<f32> {
+        self.0.multibody_joint_anchor_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn multibodyJointSeparationColor(&self) -> Vec<f32> {
+        self.0.multibody_joint_separation_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn sleepColorMultiplier(&self) -> Vec<f32> {
+        self.0.sleep_color_multiplier.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn disabledColorMultiplier(&self) -> Vec<f32> {
+        self.0.disabled_color_multiplier.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rigidBodyAxesLength(&self) -> f32 {
+        self.0.rigid_body_axes_length
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn contactDepthColor(&self) -> Vec<f32> {
+        self.0.contact_depth_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn contactNormalColor(&self) -> Vec<f32> {
+        self.0.contact_normal_color.to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn contactNormalLength(&self) -> f32 {
+        self.0.contact_normal_length
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn colliderAabbColor(&self) -> Vec<f32> {
+        self.0.collider_aabb_color.to_vec()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_subdivisions(&mut self, value: u32) {
+        self.0.subdivisions = value;
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_borderSubdivisions(&mut self, value: u32) {
+        self.0.border_subdivisions = value;
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_colliderDynamicColor(&mut self, value: Vec<f32>) {
+        self.0.collider_dynamic_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_colliderFixedColor(&mut self, value: Vec<f32>) {
+        self.0.collider_fixed_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_colliderKinematicColor(&mut self, value: Vec<f32>) {
+        self.0.collider_kinematic_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_colliderParentlessColor(&mut self, value: Vec<f32>) {
+        self.0.collider_parentless_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_impulseJointAnchorColor(&mut self, value: Vec<f32>) {
+        self.0.impulse_joint_anchor_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_impulseJointSeparationColor(&mut self, value: Vec<f32>) {
+        self.0.impulse_joint_separation_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_multibodyJointAnchorColor(&mut self, value: Vec<f32>) {
+        self.0.multibody_joint_anchor_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_multibodyJointSeparationColor(&mut self, value: Vec<f32>) {
+        self.0.multibody_joint_separation_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_sleepColorMultiplier(&mut self, value: Vec<f32>) {
+        self.0.sleep_color_multiplier = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_disabledColorMultiplier(&mut self, value: Vec<f32>) {
+        self.0.disabled_color_multiplier = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_rigidBodyAxesLength(&mut self, value: f32) {
+        self.0.rigid_body_axes_length = value;
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_contactDepthColor(&mut self, value: Vec<f32>) {
+        self.0.contact_depth_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_contactNormalColor(&mut self, value: Vec<f32>) {
+        self.0.contact_normal_color = debug_color(&value);
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_contactNormalLength(&mut self, value: f32) {
+        self.0.contact_normal_length = value;
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_colliderAabbColor(&mut self, value: Vec<f32>) {
+        self.0.collider_aabb_color = debug_color(&value);
+    }
+}
+
+/// Reads a color from up to four HSLA components, the missing ones being set to `1.0`.
+fn debug_color(value: &[f32]) -> DebugColor {
+    let mut color = [1.0; 4];
+    for (c, v) in color.iter_mut().zip(value.iter()) {
+        *c = *v;
+    }
+    color
+}
+
+/// The vertices and triangles of a triangle mesh.
+type TriMeshBuffers = (Vec<Point<Real>>, Vec<[u32; 3]>);
+
+/// Appends the triangle mesh approximating `shape`, transformed by `pos`, to the given buffers.
+fn push_shape_triangles(
+    shape: &dyn Shape,
+    pos: &Isometry<Real>,
+    subdivisions: u32,
+    vertices: &mut Vec<f32>,
+    indices: &mut Vec<u32>,
+) {
+    if let Some(compound) = shape.as_compound() {
+        for (sub_pos, sub_shape) in compound.shapes() {
+            push_shape_triangles(
+                &**sub_shape,
+                &(pos * sub_pos),
+                subdivisions,
+                vertices,
+                indices,
+            );
+        }
+        return;
+    }
+
+    let Some((points, triangles)) = shape_trimesh(shape, subdivisions) else {
+        return;
+    };
+
+    let base_id = (vertices.len() / DIM) as u32;
+    for pt in points {
+        vertices.extend_from_slice((pos * pt).coords.as_slice());
+    }
+    for tri in triangles {
+        indices.extend_from_slice(&[base_id + tri[0], base_id + tri[1], base_id + tri[2]]);
+    }
+}
+
+/// Tessellates the given shape with the shapes’ `to_trimesh` methods.
+///
+/// Rounded shapes, segments, polylines, and halfspaces have no triangle mesh.
+#[cfg(feature = "dim3")]
+fn shape_trimesh(shape: &dyn Shape, subdivisions: u32) -> Option<TriMeshBuffers> {
+    let nphi_subdivisions = (subdivisions / 2).max(2);
+
+    match shape.shape_type() {
+        ShapeType::Ball => shape
+            .as_ball()
+            .map(|s| s.to_trimesh(subdivisions, nphi_subdivisions)),
+        ShapeType::Cuboid => shape.as_cuboid().map(|s| s.to_trimesh()),
+        ShapeType::Capsule => shape
+            .as_capsule()
+            .map(|s| s.to_trimesh(subdivisions, nphi_subdivisions)),
+        ShapeType::Cylinder => shape.as_cylinder().map(|s| s.to_trimesh(subdivisions)),
+        ShapeType::Cone => shape.as_cone().map(|s| s.to_trimesh(subdivisions)),
+        ShapeType::ConvexPolyhedron => shape.as_convex_polyhedron().map(|s| s.to_trimesh()),
+        ShapeType::Triangle => shape
+            .as_triangle()
+            .map(|t| (vec![t.a, t.b, t.c], vec![[0, 1, 2]])),
+        ShapeType::TriMesh => shape
+            .as_trimesh()
+            .map(|t| (t.vertices().to_vec(), t.indices().to_vec())),
+        ShapeType::HeightField => shape.as_heightfield().map(|s| s.to_trimesh()),
+        ShapeType::Voxels => shape.as_voxels().map(|s| s.to_trimesh()),
+        _ => None,
+    }
+}
+
+/// Tessellates the given shape, triangulating the polylines of convex shapes as fans.
+///
+/// Heightfields are rendered as a thin strip below their segments. Rounded shapes, segments,
+/// polylines, and halfspaces have no triangle mesh.
+#[cfg(feature = "dim2")]
+fn shape_trimesh(shape: &dyn Shape, subdivisions: u32) -> Option<TriMeshBuffers> {
+    match shape.shape_type() {
+        ShapeType::Ball => shape
+            .as_ball()
+            .map(|s| convex_polygon_trimesh(s.to_polyline(subdivisions))),
+        ShapeType::Cuboid => shape.as_cuboid().map(|s| s.to_trimesh()),
+        ShapeType::Capsule => shape
+            .as_capsule()
+            .map(|s| convex_polygon_trimesh(s.to_polyline(subdivisions))),
+        ShapeType::ConvexPolygon => shape
+            .as_convex_polygon()
+            .map(|s| convex_polygon_trimesh(s.points().to_vec())),
+        ShapeType::Triangle => shape
+            .as_triangle()
+            .map(|t| (vec![t.a, t.b, t.c], vec![[0, 1, 2]])),
+        ShapeType::TriMesh => shape
+            .as_trimesh()
+            .map(|t| (t.vertices().to_vec(), t.indices().to_vec())),
+        ShapeType::HeightField => shape.as_heightfield().map(heightfield_trimesh),
+        ShapeType::Voxels => shape.as_voxels().map(voxels_trimesh),
+        _ => None,
+    }
+}
+
+/// Triangulates a 2D heightfield as a strip, as thick as a tenth of a cell, below its segments.
+#[cfg(feature = "dim2")]
+fn heightfield_trimesh(heightfield: &HeightField) -> TriMeshBuffers {
+    let offset = Vector::y() * (heightfield.cell_width() * 0.1);
+    let mut vtx = vec![];
+    let mut idx = vec![];
+
+    for seg in heightfield.segments() {
+        let base_id = vtx.len() as u32;
+        vtx.extend([seg.a, seg.a - offset, seg.b - offset, seg.b]);
+        idx.extend([
+            [base_id, base_id + 1, base_id + 2],
+            [base_id, base_id + 2, base_id + 3],
+        ]);
+    }
+
+    (vtx, idx)
+}
+
+#[cfg(feature = "dim2")]
+fn convex_polygon_trimesh(points: Vec<Point<Real>>) -> TriMeshBuffers {
+    let indices = (1..(points.len() as u32).saturating_sub(1))
+        .map(|i| [0, i, i + 1])
+        .collect();
+    (points, indices)
+}
+
+#[cfg(feature = "dim2")]
+fn voxels_trimesh(voxels: &Voxels) -> TriMeshBuffers {
+    let (quad_vtx, quad_idx) = Cuboid::new(voxels.voxel_size() / 2.0).to_trimesh();
+    let mut vtx = vec![];
+    let mut idx = vec![];
+
+    for vox in voxels.voxels().filter(|vox| !vox.state.is_empty()) {
+        let base_id = vtx.len() as u32;
+        vtx.extend(quad_vtx.iter().map(|pt| vox.center + pt.coords));
+        idx.extend(
+            quad_idx
+                .iter()
+                .map(|tri| [base_id + tri[0], base_id + tri[1], base_id + tri[2]]),
+        );
+    }
+
+    (vtx, idx)
+}
+
 struct CopyToBuffersBackend<'a> {
     filter: QueryFilter<'a>,
     bodies: &'a RigidBodySet,
     colliders: &'a ColliderSet,
     vertices: &'a mut Vec<f32>,
     colors: &'a mut Vec<f32>,
+    objects: Option<&'a mut Vec<f64>>,
 }
 
 impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
@@ -134,7 +655,7 @@ impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
     /// Note that this method can be called multiple time for the same `object`.
     fn draw_line(
         &mut self,
-        _object: DebugRenderObject,
+        object: DebugRenderObject,
         a: Point<Real>,
         b: Point<Real>,
         color: [f32; 4],
@@ -148,5 +669,36 @@ impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
         self.colors.extend_from_slice(&[
             rgb.red, rgb.green, rgb.blue, rgb.alpha, rgb.red, rgb.green, rgb.blue, rgb.alpha,
         ]);
+
+        if let Some(objects) = &mut self.objects {
+            let (object_type, handle1, handle2) = match object {
+                DebugRenderObject::RigidBody(handle, _) => {
+                    (RawDebugRenderObjectType::RigidBody, handle.0, None)
+                }
+                DebugRenderObject::Collider(handle, _) => {
+                    (RawDebugRenderObjectType::Collider, handle.0, None)
+                }
+                DebugRenderObject::ColliderAabb(handle, _, _) => {
+                    (RawDebugRenderObjectType::ColliderAabb, handle.0, None)
+                }
+                DebugRenderObject::ImpulseJoint(handle, _) => {
+                    (RawDebugRenderObjectType::ImpulseJoint, handle.0, None)
+                }
+                DebugRenderObject::MultibodyJoint(handle, _, _) => {
+                    (RawDebugRenderObjectType::MultibodyJoint, handle.0, None)
+                }
+                DebugRenderObject::ContactPair(pair, _, _) => (
+                    RawDebugRenderObjectType::ContactPair,
+                    pair.collider1.0,
+                    Some(pair.collider2.0),
+                ),
+            };
+
+            objects.extend_from_slice(&[
+                object_type as u32 as f64,
+                utils::flat_handle(handle1),
+                handle2.map(utils::flat_handle).unwrap_or(-1.0),
+            ]);
+        }
     }
 }
@@ -0,0 +1,236 @@
+use super::serialization_pipeline::{
+    DeserializableWorld, SerializableWorld, SnapshotHeader, SNAPSHOT_MAGIC,
+};
+use crate::dynamics::{
+    RawImpulseJointSet, RawIntegrationParameters, RawIslandManager, RawMultibodyJointSet,
+    RawRigidBodySet,
+};
+use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
+use crate::math::RawVector;
+use crate::pipeline::{RawDeserializedWorld, RawSerializationPipeline};
+use crate::utils;
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+/// Magic number at the beginning of every delta snapshot created by `serializeDelta`.
+const DELTA_MAGIC: [u8; 4] = *b"RPJD";
+/// Size, in bytes, of the blocks compared between the base snapshot and the current state.
+///
+/// Most of the state of a rigid-body or collider has a fixed size once serialized. So, when
+/// only a few of them changed, only the blocks containing their data end up in the delta.
+const DELTA_BLOCK_SIZE: usize = 16;
+
+/// Serializes each component of the world separately.
+///
+/// The concatenation of all the components is identical to the serialization of the whole world.
+fn serialize_components(world: &SerializableWorld) -> bincode::Result<Vec<Vec<u8>>> {
+    Ok(vec![
+        bincode::serialize(world.gravity)?,
+        bincode::serialize(world.integration_parameters)?,
+        bincode::serialize(world.islands)?,
+        bincode::serialize(world.broad_phase)?,
+        bincode::serialize(world.narrow_phase)?,
+        bincode::serialize(world.bodies)?,
+        bincode::serialize(world.colliders)?,
+        bincode::serialize(world.impulse_joints)?,
+        bincode::serialize(world.multibody_joints)?,
+        bincode::serialize(world.impulse_joint_break_thresholds)?,
+    ])
+}
+
+/// The difference between one serialized world component and its base.
+#[derive(Serialize, Deserialize)]
+enum ComponentDelta {
+    /// The component is identical to its base.
+    Unchanged,
+    /// The component has the same size as its base. Only the byte ranges that differ are stored,
+    /// as `(offset, bytes)` pairs.
+    Patched(Vec<(u32, Vec<u8>)>),
+    /// The component doesn’t have the same size as its base, so it is stored completely.
+    Replaced(Vec<u8>),
+}
+
+impl ComponentDelta {
+    fn new(base: &[u8], current: Vec<u8>) -> Self {
+        if base.len() != current.len() {
+            return Self::Replaced(current);
+        }
+
+        let mut patches: Vec<(u32, Vec<u8>)> = vec![];
+        let blocks = base
+            .chunks(DELTA_BLOCK_SIZE)
+            .zip(current.chunks(DELTA_BLOCK_SIZE));
+
+        for (i, (base_block, block)) in blocks.enumerate() {
+            if base_block != block {
+                let offset = i * DELTA_BLOCK_SIZE;
+                // Merge consecutive modified blocks into a single patch.
+                match patches.last_mut() {
+                    Some((start, bytes)) if *start as usize + bytes.len() == offset => {
+                        bytes.extend_from_slice(block)
+                    }
+                    _ => patches.push((offset as u32, block.to_vec())),
+                }
+            }
+        }
+
+        if patches.is_empty() {
+            Self::Unchanged
+        } else {
+            Self::Patched(patches)
+        }
+    }
+
+    fn apply(self, base: &[u8], out: &mut Vec<u8>) -> Result<(), JsError> {
+        match self {
+            Self::Unchanged => out.extend_from_slice(base),
+            Self::Replaced(data) => out.extend_from_slice(&data),
+            Self::Patched(patches) => {
+                let start = out.len();
+                out.extend_from_slice(base);
+                let component = &mut out[start..];
+
+                for (offset, bytes) in patches {
+                    let offset = offset as usize;
+                    component
+                        .get_mut(offset..offset + bytes.len())
+                        .ok_or_else(|| {
+                            JsError::new("Invalid delta snapshot: patch out of bounds.")
+                        })?
+                        .copy_from_slice(&bytes);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WorldDelta {
+    /// The hash of the base snapshot this delta was computed from.
+    base_hash: u64,
+    components: Vec<ComponentDelta>,
+}
+
+/// A full snapshot prepared for computing and applying delta snapshots.
+///
+/// Preparing the base is about as expensive as restoring a full snapshot, so the same base
+/// should be reused for as many delta snapshots as possible.
+#[wasm_bindgen]
+pub struct RawDeltaSnapshotBase {
+    components: Vec<Vec<u8>>,
+    hash: u64,
+}
+
+#[wasm_bindgen]
+impl RawDeltaSnapshotBase {
+    /// Prepares a snapshot created by `RawSerializationPipeline.serializeAll` for being used as
+    /// the base of delta snapshots.
+    ///
+    /// Throws an error if the snapshot is invalid, or if it was created by a different version
+    /// or flavor (dimension and feature set) of the library.
+    #[wasm_bindgen(constructor)]
+    pub fn new(snapshot: Uint8Array) -> Result<RawDeltaSnapshotBase, JsError> {
+        let data = snapshot.to_vec();
+        let payload = SnapshotHeader::read(SNAPSHOT_MAGIC, &data)?;
+        let world: DeserializableWorld = bincode::deserialize(payload)
+            .map_err(|e| JsError::new(&format!("Invalid snapshot: {}", e)))?;
+        let components = serialize_components(&world.as_serializable())
+            .map_err(|e| JsError::new(&format!("Invalid snapshot: {}", e)))?;
+        let hash = components
+            .iter()
+            .fold(utils::FNV1A_INIT, |hash, component| {
+                utils::fnv1a(hash, component)
+            });
+
+        Ok(Self { components, hash })
+    }
+}
+
+#[wasm_bindgen]
+impl RawSerializationPipeline {
+    /// Serializes the difference between the current physics state and the given base snapshot.
+    ///
+    /// Only the parts of the serialized state that changed since the base snapshot are stored.
+    /// In particular, rigid-bodies and colliders that didn’t change don’t take any space. The
+    /// broad-phase and narrow-phase are stored too, so the contacts and their warm-starting
+    /// impulses are restored exactly. They usually end up stored completely, since their
+    /// serialized size changes whenever contacts are added or removed.
+    pub fn serializeDelta(
+        &self,
+        base: &RawDeltaSnapshotBase,
+        gravity: &RawVector,
+        integrationParameters: &RawIntegrationParameters,
+        islands: &RawIslandManager,
+        broadPhase: &RawBroadPhase,
+        narrowPhase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> Result<Uint8Array, JsError> {
+        let to_serialize = SerializableWorld {
+            gravity: &gravity.0,
+            integration_parameters: &integrationParameters.0,
+            islands: &islands.0,
+            broad_phase: &broadPhase.0,
+            narrow_phase: &narrowPhase.0,
+            bodies: &bodies.0,
+            colliders: &colliders.0,
+            impulse_joints: &impulse_joints.0,
+            multibody_joints: &multibody_joints.0,
+            impulse_joint_break_thresholds: &impulse_joints.1,
+        };
+        let to_error = |e| JsError::new(&format!("Cannot serialize delta snapshot: {}", e));
+        let components = serialize_components(&to_serialize).map_err(to_error)?;
+        let delta = WorldDelta {
+            base_hash: base.hash,
+            components: base
+                .components
+                .iter()
+                .zip(components)
+                .map(|(base, current)| ComponentDelta::new(base, current))
+                .collect(),
+        };
+
+        let mut data = vec![];
+        SnapshotHeader::write(DELTA_MAGIC, &mut data)
+            .and_then(|_| bincode::serialize_into(&mut data, &delta))
+            .map_err(to_error)?;
+        Ok(Uint8Array::from(&data[..]))
+    }
+
+    /// Deserializes a delta snapshot created by `serializeDelta` with the same base.
+    ///
+    /// The resulting physics state is bit-for-bit identical to the state that was serialized.
+    ///
+    /// Throws an error if the delta snapshot is invalid, if it was computed from a different
+    /// base, or if it was created by a different version or flavor (dimension and feature set)
+    /// of the library.
+    pub fn deserializeDelta(
+        &self,
+        base: &RawDeltaSnapshotBase,
+        data: Uint8Array,
+    ) -> Result<RawDeserializedWorld, JsError> {
+        let data = data.to_vec();
+        let payload = SnapshotHeader::read(DELTA_MAGIC, &data)?;
+        let delta: WorldDelta = bincode::deserialize(payload)
+            .map_err(|e| JsError::new(&format!("Invalid delta snapshot: {}", e)))?;
+
+        if delta.base_hash != base.hash || delta.components.len() != base.components.len() {
+            return Err(JsError::new(
+                "Invalid delta snapshot: it was computed from a different base snapshot.",
+            ));
+        }
+
+        let mut world = vec![];
+        for (component, base) in delta.components.into_iter().zip(base.components.iter()) {
+            component.apply(base, &mut world)?;
+        }
+
+        let d: DeserializableWorld = bincode::deserialize(&world)
+            .map_err(|e| JsError::new(&format!("Invalid delta snapshot: {}", e)))?;
+        Ok(d.into_raw())
+    }
+}
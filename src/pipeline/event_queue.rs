@@ -1,11 +1,30 @@
 use crate::math::RawVector;
 use crate::utils;
 use crate::utils::FlatHandle;
+use rapier::dynamics::{
+    ImpulseJointHandle, ImpulseJointSet, IslandManager, RigidBodyHandle, RigidBodySet,
+};
 use rapier::geometry::{CollisionEvent, ContactForceEvent};
+use rapier::math::DIM;
 use rapier::pipeline::ChannelEventCollector;
+use std::collections::HashSet;
 use std::sync::mpsc::Receiver;
 use wasm_bindgen::prelude::*;
 
+/// Number of floats used to encode one collision event by `RawEventQueue::drainCollisionEventsPacked`:
+/// `[collider1, collider2, started, flags]`.
+const COLLISION_EVENT_STRIDE: usize = 4;
+/// Number of floats used to encode one contact force event by
+/// `RawEventQueue::drainContactForceEventsPacked`: `[collider1, collider2, total_force (DIM floats),
+/// total_force_magnitude, max_force_direction (DIM floats), max_force_magnitude]`.
+const CONTACT_FORCE_EVENT_STRIDE: usize = 4 + 2 * DIM;
+/// Number of floats used to encode one sleep event by `RawEventQueue::drainSleepEventsPacked`:
+/// `[body, sleeping]`.
+const SLEEP_EVENT_STRIDE: usize = 2;
+/// Number of floats used to encode one joint break event by
+/// `RawEventQueue::drainJointBreakEventsPacked`: `[joint, body1, body2]`.
+const JOINT_BREAK_EVENT_STRIDE: usize = 3;
+
 /// A structure responsible for collecting events generated
 /// by the physics engine.
 #[wasm_bindgen]
@@ -13,9 +32,59 @@ pub struct RawEventQueue {
     pub(crate) collector: ChannelEventCollector,
     collision_events: Receiver<CollisionEvent>,
     contact_force_events: Receiver<ContactForceEvent>,
+    /// The dynamic rigid-bodies that were awake at the beginning of the current step.
+    awake_bodies: Vec<RigidBodyHandle>,
+    /// The rigid-bodies that fell asleep (`true`) or woke up (`false`).
+    sleep_events: Vec<(RigidBodyHandle, bool)>,
+    /// The impulse joints that broke, with the rigid-bodies they were attached to.
+    joint_break_events: Vec<(ImpulseJointHandle, RigidBodyHandle, RigidBodyHandle)>,
     pub(crate) auto_drain: bool,
 }
 
+#[wasm_bindgen]
+pub struct RawCollisionEvent(CollisionEvent);
+
+#[wasm_bindgen]
+impl RawCollisionEvent {
+    /// The first collider involved in the collision.
+    pub fn collider1(&self) -> FlatHandle {
+        crate::utils::flat_handle(self.0.collider1().0)
+    }
+
+    /// The second collider involved in the collision.
+    pub fn collider2(&self) -> FlatHandle {
+        crate::utils::flat_handle(self.0.collider2().0)
+    }
+
+    /// Is this a `Started` collision event?
+    pub fn started(&self) -> bool {
+        self.0.started()
+    }
+
+    /// Is this a `Stopped` collision event?
+    pub fn stopped(&self) -> bool {
+        self.0.stopped()
+    }
+
+    /// The bit flags giving more information about this event (see `CollisionEventFlags`).
+    pub fn flags(&self) -> u32 {
+        let flags = match self.0 {
+            CollisionEvent::Started(_, _, flags) | CollisionEvent::Stopped(_, _, flags) => flags,
+        };
+        flags.bits()
+    }
+
+    /// Was at least one of the colliders involved in the collision a sensor when the event was fired?
+    pub fn sensor(&self) -> bool {
+        self.0.sensor()
+    }
+
+    /// Was this `Stopped` event fired because at least one of the colliders was removed?
+    pub fn removed(&self) -> bool {
+        self.0.removed()
+    }
+}
+
 #[wasm_bindgen]
 pub struct RawContactForceEvent(ContactForceEvent);
 
@@ -86,6 +155,9 @@ impl RawEventQueue {
             collector,
             collision_events: collision_channel.1,
             contact_force_events: contact_force_channel.1,
+            awake_bodies: vec![],
+            sleep_events: vec![],
+            joint_break_events: vec![],
             auto_drain: autoDrain,
         }
     }
@@ -126,6 +198,22 @@ impl RawEventQueue {
         }
     }
 
+    /// Applies the given javascript closure on each collision event of this collector, then clear
+    /// the internal collision event buffer.
+    ///
+    /// Unlike `drainCollisionEvents`, the closure is given a `RawCollisionEvent` which also exposes
+    /// the event’s flags, e.g., to distinguish sensor events or events caused by a collider removal.
+    ///
+    /// # Parameters
+    /// - `f(event)`: JavaScript closure applied to each collision event. The closure should take a
+    /// single `RawCollisionEvent` argument.
+    pub fn drainCollisionEventObjects(&mut self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        while let Ok(event) = self.collision_events.try_recv() {
+            let _ = f.call1(&this, &JsValue::from(RawCollisionEvent(event)));
+        }
+    }
+
     pub fn drainContactForceEvents(&mut self, f: &js_sys::Function) {
         let this = JsValue::null();
         while let Ok(event) = self.contact_force_events.try_recv() {
@@ -133,8 +221,219 @@ impl RawEventQueue {
         }
     }
 
+    /// The number of floats used to encode one event in the array returned by
+    /// `drainCollisionEventsPacked`.
+    pub fn collisionEventStride() -> usize {
+        COLLISION_EVENT_STRIDE
+    }
+
+    /// The number of floats used to encode one event in the array returned by
+    /// `drainContactForceEventsPacked`.
+    pub fn contactForceEventStride() -> usize {
+        CONTACT_FORCE_EVENT_STRIDE
+    }
+
+    /// Returns all the collision events of this collector packed into a single array, then clear
+    /// the internal collision event buffer.
+    ///
+    /// Each event occupies `collisionEventStride()` consecutive elements:
+    /// `[collider1, collider2, started, flags]` where `started` is `1` if the collision started and
+    /// `0` if it stopped, and `flags` are the bits of the event’s `CollisionEventFlags`.
+    pub fn drainCollisionEventsPacked(&mut self) -> Vec<f64> {
+        let mut result = vec![];
+        while let Ok(event) = self.collision_events.try_recv() {
+            let raw = RawCollisionEvent(event);
+            result.extend_from_slice(&[
+                raw.collider1(),
+                raw.collider2(),
+                raw.started() as u32 as f64,
+                raw.flags() as f64,
+            ]);
+        }
+        result
+    }
+
+    /// Returns all the contact force events of this collector packed into a single array, then
+    /// clear the internal contact force event buffer.
+    ///
+    /// Each event occupies `contactForceEventStride()` consecutive elements:
+    /// `[collider1, collider2, total_force, total_force_magnitude, max_force_direction, max_force_magnitude]`
+    /// where `total_force` and `max_force_direction` are vectors with two (in 2D) or three (in 3D)
+    /// components.
+    pub fn drainContactForceEventsPacked(&mut self) -> Vec<f64> {
+        let mut result = vec![];
+        while let Ok(event) = self.contact_force_events.try_recv() {
+            result.push(utils::flat_handle(event.collider1.0));
+            result.push(utils::flat_handle(event.collider2.0));
+            result.extend(event.total_force.iter().map(|x| *x as f64));
+            result.push(event.total_force_magnitude as f64);
+            result.extend(event.max_force_direction.iter().map(|x| *x as f64));
+            result.push(event.max_force_magnitude as f64);
+        }
+        result
+    }
+
+    /// Applies the given javascript closure on each sleep event of this collector, then clear the
+    /// internal sleep event buffer.
+    ///
+    /// A sleep event is generated whenever a dynamic rigid-body falls asleep or wakes up during a
+    /// step performed with this collector, in the order these changes were detected.
+    ///
+    /// # Parameters
+    /// - `f(handle, sleeping)`: JavaScript closure applied to each sleep event. The closure should
+    /// take two arguments: the handle of the rigid-body, and a boolean indicating if it fell asleep
+    /// (true) or woke up (false).
+    pub fn drainSleepEvents(&mut self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for (handle, sleeping) in self.sleep_events.drain(..) {
+            let _ = f.call2(
+                &this,
+                &JsValue::from(utils::flat_handle(handle.0)),
+                &JsValue::from_bool(sleeping),
+            );
+        }
+    }
+
+    /// The number of floats used to encode one event in the array returned by
+    /// `drainSleepEventsPacked`.
+    pub fn sleepEventStride() -> usize {
+        SLEEP_EVENT_STRIDE
+    }
+
+    /// Returns all the sleep events of this collector packed into a single array, then clear the
+    /// internal sleep event buffer.
+    ///
+    /// Each event occupies `sleepEventStride()` consecutive elements: `[body, sleeping]` where
+    /// `sleeping` is `1` if the rigid-body fell asleep and `0` if it woke up.
+    pub fn drainSleepEventsPacked(&mut self) -> Vec<f64> {
+        let mut result = vec![];
+        for (handle, sleeping) in self.sleep_events.drain(..) {
+            result.extend_from_slice(&[utils::flat_handle(handle.0), sleeping as u32 as f64]);
+        }
+        result
+    }
+
+    /// Applies the given javascript closure on each joint break event of this collector, then
+    /// clear the internal joint break event buffer.
+    ///
+    /// A joint break event is generated whenever a breakable impulse joint breaks during a step
+    /// performed with this collector.
+    ///
+    /// # Parameters
+    /// - `f(joint, body1, body2)`: JavaScript closure applied to each joint break event. The
+    /// closure should take three arguments: the handle of the joint that broke, and the handles of
+    /// the two rigid-bodies it was attached to.
+    pub fn drainJointBreakEvents(&mut self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for (joint, body1, body2) in self.joint_break_events.drain(..) {
+            let _ = f.call3(
+                &this,
+                &JsValue::from(utils::flat_handle(joint.0)),
+                &JsValue::from(utils::flat_handle(body1.0)),
+                &JsValue::from(utils::flat_handle(body2.0)),
+            );
+        }
+    }
+
+    /// The number of floats used to encode one event in the array returned by
+    /// `drainJointBreakEventsPacked`.
+    pub fn jointBreakEventStride() -> usize {
+        JOINT_BREAK_EVENT_STRIDE
+    }
+
+    /// Returns all the joint break events of this collector packed into a single array, then
+    /// clear the internal joint break event buffer.
+    ///
+    /// Each event occupies `jointBreakEventStride()` consecutive elements: `[joint, body1, body2]`.
+    pub fn drainJointBreakEventsPacked(&mut self) -> Vec<f64> {
+        let mut result = vec![];
+        for (joint, body1, body2) in self.joint_break_events.drain(..) {
+            result.extend_from_slice(&[
+                utils::flat_handle(joint.0),
+                utils::flat_handle(body1.0),
+                utils::flat_handle(body2.0),
+            ]);
+        }
+        result
+    }
+
     /// Removes all events contained by this collector.
-    pub fn clear(&self) {
+    pub fn clear(&mut self) {
         while let Ok(_) = self.collision_events.try_recv() {}
+        self.sleep_events.clear();
+        self.joint_break_events.clear();
+    }
+}
+
+/// Is the given rigid-body dynamic and awake?
+fn is_awake(bodies: &RigidBodySet, handle: RigidBodyHandle) -> bool {
+    bodies
+        .get(handle)
+        .map(|rb| rb.is_dynamic() && !rb.is_sleeping())
+        .unwrap_or(false)
+}
+
+impl RawEventQueue {
+    /// Records which dynamic rigid-bodies are awake before a step, for `collect_sleep_events`.
+    ///
+    /// This is recomputed at each step instead of being kept from the previous one, so the sleep
+    /// events stay correct if this queue is used with another world, or if the world is restored
+    /// from a checkpoint or snapshot between two steps.
+    pub(crate) fn begin_sleep_events(&mut self, bodies: &RigidBodySet, islands: &IslandManager) {
+        self.awake_bodies.clear();
+        self.awake_bodies.extend(
+            islands
+                .active_bodies()
+                .iter()
+                .copied()
+                .filter(|handle| is_awake(bodies, *handle)),
+        );
+    }
+
+    /// Generates the sleep events of the dynamic rigid-bodies that fell asleep or woke up since
+    /// the last call to `begin_sleep_events`, by comparing them to the current active set.
+    ///
+    /// Dynamic rigid-bodies inserted awake generate a wake-up event at the first step after their
+    /// insertion. Removed rigid-bodies don’t generate any event.
+    pub(crate) fn collect_sleep_events(&mut self, bodies: &RigidBodySet, islands: &IslandManager) {
+        let was_awake: HashSet<_> = self.awake_bodies.iter().copied().collect();
+        let mut awake_bodies = vec![];
+
+        for handle in islands.active_bodies() {
+            if is_awake(bodies, *handle) {
+                awake_bodies.push(*handle);
+                if !was_awake.contains(handle) {
+                    self.sleep_events.push((*handle, false));
+                }
+            }
+        }
+
+        let awake_now: HashSet<_> = awake_bodies.iter().copied().collect();
+        for handle in &self.awake_bodies {
+            let is_sleeping = bodies
+                .get(*handle)
+                .map(|rb| rb.is_sleeping())
+                .unwrap_or(false);
+
+            if is_sleeping && !awake_now.contains(handle) {
+                self.sleep_events.push((*handle, true));
+            }
+        }
+
+        self.awake_bodies = awake_bodies;
+    }
+
+    /// Generates the joint break events of the given broken impulse joints.
+    pub(crate) fn push_joint_break_events(
+        &mut self,
+        joints: &ImpulseJointSet,
+        broken: &[ImpulseJointHandle],
+    ) {
+        for handle in broken {
+            if let Some(joint) = joints.get(*handle) {
+                self.joint_break_events
+                    .push((*handle, joint.body1, joint.body2));
+            }
+        }
     }
 }
@@ -1,11 +1,18 @@
 pub use self::debug_render_pipeline::*;
+pub use self::delta_snapshot::*;
 pub use self::event_queue::*;
 pub use self::physics_hooks::*;
 pub use self::physics_pipeline::*;
 pub use self::serialization_pipeline::*;
+pub use self::world_checkpoint::*;
+pub use self::world_hash::*;
 
 mod debug_render_pipeline;
+mod delta_snapshot;
 mod event_queue;
 mod physics_hooks;
 mod physics_pipeline;
+mod scene;
 mod serialization_pipeline;
+mod world_checkpoint;
+mod world_hash;
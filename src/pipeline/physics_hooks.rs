@@ -1,13 +1,18 @@
-use crate::utils;
+use crate::math::RawVector;
+use crate::utils::{self, FlatHandle};
 use rapier::geometry::SolverFlags;
+use rapier::math::Real;
 use rapier::pipeline::{ContactModificationContext, PairFilterContext, PhysicsHooks};
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 
 pub struct RawPhysicsHooks {
     pub this: js_sys::Object,
     pub filter_contact_pair: js_sys::Function,
     pub filter_intersection_pair: js_sys::Function,
-    // pub modify_solver_contacts: &'a js_sys::Function,
+    pub modify_solver_contacts: js_sys::Function,
 }
 
 // HACK: the RawPhysicsHooks is no longer Send+Sync because the JS objects are
@@ -23,6 +28,9 @@ extern "C" {
     // `log(..)`
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
+
+    #[wasm_bindgen(js_namespace = console)]
+    fn error(err: &JsValue);
 }
 
 impl PhysicsHooks for RawPhysicsHooks {
@@ -73,145 +81,227 @@ impl PhysicsHooks for RawPhysicsHooks {
             .unwrap_or(false)
     }
 
-    fn modify_solver_contacts(&self, _ctxt: &mut ContactModificationContext) {}
-}
+    fn modify_solver_contacts(&self, ctxt: &mut ContactModificationContext) {
+        if !self.modify_solver_contacts.is_function() {
+            return;
+        }
 
-/* NOTE: the following is an attempt to make contact modification work.
- *
-#[wasm_bindgen]
-#[derive(Copy, Clone, Debug)]
-pub struct RawContactManifold(*const ContactManifold);
-pub struct RawSolverContact(*const SolverContact);
+        let raw_ctxt = RawContactModificationContext::new(ctxt);
+        let valid = raw_ctxt.ctxt.clone();
+        let result = self
+            .modify_solver_contacts
+            .call1(&self.this, &JsValue::from(raw_ctxt));
+        // The context given to JS may outlive this call (if the user keeps a
+        // reference to it). Invalidate it so it can no longer reach `ctxt`.
+        valid.set(None);
 
+        // The error can’t be propagated through the physics pipeline, so at
+        // least report it instead of silently ignoring the hook’s failure.
+        if let Err(err) = result {
+            error(&err);
+        }
+    }
+}
+
+/// The contact modification context given to the `modifySolverContacts` physics hook.
+///
+/// This gives read/write access to the solver contacts, the contact normal, and the user-data
+/// of a single contact manifold. It is only valid for the duration of the hook call: once the
+/// hook returns, every getter returns a default value and every setter is a no-op.
 #[wasm_bindgen]
 pub struct RawContactModificationContext {
-    pub collider1: u32,
-    pub collider2: u32,
-    pub rigid_body1: Option<u32>,
-    pub rigid_body2: Option<u32>,
-    pub manifold: *const ContactManifold,
-    pub solver_contacts: *mut Vec<SolverContact>,
-    normal: *mut Vector<Real>,
-    user_data: *mut u32,
+    collider1: FlatHandle,
+    collider2: FlatHandle,
+    rigid_body1: Option<FlatHandle>,
+    rigid_body2: Option<FlatHandle>,
+    // SAFETY: this pointer is set only while the `modify_solver_contacts` hook is executing,
+    //         i.e., while the `ContactModificationContext` it points to is exclusively borrowed
+    //         by us. It is reset to `None` right after the JS callback returns.
+    ctxt: Rc<Cell<Option<NonNull<ContactModificationContext<'static>>>>>,
+}
+
+impl RawContactModificationContext {
+    fn new(ctxt: &mut ContactModificationContext) -> Self {
+        Self {
+            collider1: utils::flat_handle(ctxt.collider1.0),
+            collider2: utils::flat_handle(ctxt.collider2.0),
+            rigid_body1: ctxt.rigid_body1.map(|h| utils::flat_handle(h.0)),
+            rigid_body2: ctxt.rigid_body2.map(|h| utils::flat_handle(h.0)),
+            ctxt: Rc::new(Cell::new(Some(NonNull::from(ctxt).cast()))),
+        }
+    }
+
+    fn with_ctxt<T>(&self, f: impl FnOnce(&mut ContactModificationContext) -> T) -> Option<T> {
+        // SAFETY: see the comment on `Self::ctxt`. None of the closures passed here
+        //         call back into JS, so no other reference to the context can exist
+        //         while `f` runs.
+        self.ctxt.get().map(|mut ptr| unsafe { f(ptr.as_mut()) })
+    }
 }
 
 #[wasm_bindgen]
 impl RawContactModificationContext {
-    pub fn collider1(&self) -> u32 {
+    /// Is this context still usable, i.e., is the hook it was given to still running?
+    pub fn isValid(&self) -> bool {
+        self.ctxt.get().is_some()
+    }
+
+    /// The handle of the first collider involved in the contact.
+    pub fn collider1(&self) -> FlatHandle {
         self.collider1
     }
 
-    pub fn collider2(&self) -> u32 {
+    /// The handle of the second collider involved in the contact.
+    pub fn collider2(&self) -> FlatHandle {
         self.collider2
     }
 
-    #[wasm_bindgen(getter)]
-    pub fn normal(&self) -> RawVector {
-        unsafe { RawVector(*self.normal) }
+    /// The handle of the rigid-body the first collider is attached to, if any.
+    pub fn rigid_body1(&self) -> Option<FlatHandle> {
+        self.rigid_body1
     }
 
-    #[wasm_bindgen(setter)]
-    pub fn set_normal(&mut self, normal: RawVector) {
-        unsafe {
-            *self.normal = normal.0;
-        }
+    /// The handle of the rigid-body the second collider is attached to, if any.
+    pub fn rigid_body2(&self) -> Option<FlatHandle> {
+        self.rigid_body2
     }
 
-    #[wasm_bindgen(getter)]
+    /// The world-space contact normal, pointing outward from the first collider.
+    pub fn normal(&self) -> Option<RawVector> {
+        self.with_ctxt(|ctxt| RawVector(*ctxt.normal))
+    }
+
+    pub fn set_normal(&self, normal: &RawVector) {
+        self.with_ctxt(|ctxt| *ctxt.normal = normal.0);
+    }
+
+    /// The contact normal in the local-space of the first collider.
+    pub fn local_n1(&self) -> Option<RawVector> {
+        self.with_ctxt(|ctxt| ctxt.manifold.local_n1.into())
+    }
+
+    /// The contact normal in the local-space of the second collider.
+    pub fn local_n2(&self) -> Option<RawVector> {
+        self.with_ctxt(|ctxt| ctxt.manifold.local_n2.into())
+    }
+
+    /// The user-data attached to the contact manifold.
+    ///
+    /// This is persistent across timesteps, as long as the manifold exists.
     pub fn user_data(&self) -> u32 {
-        unsafe { *self.user_data }
+        self.with_ctxt(|ctxt| *ctxt.user_data).unwrap_or(0)
     }
 
-    #[wasm_bindgen(setter)]
-    pub fn set_user_data(&mut self, user_data: u32) {
-        unsafe {
-            *self.user_data = user_data;
-        }
+    pub fn set_user_data(&self, user_data: u32) {
+        self.with_ctxt(|ctxt| *ctxt.user_data = user_data);
     }
 
     pub fn num_solver_contacts(&self) -> usize {
-        unsafe { (*self.solver_contacts).len() }
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.len())
+            .unwrap_or(0)
     }
 
-    pub fn clear_solver_contacts(&mut self) {
-        unsafe { (*self.solver_contacts).clear() }
+    /// Removes all the solver contacts, i.e., the contact will be ignored by the constraints solver.
+    pub fn clear_solver_contacts(&self) {
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.clear());
     }
 
-    pub fn remove_solver_contact(&mut self, i: usize) {
-        unsafe {
-            if i < self.num_solver_contacts() {
-                (*self.solver_contacts).swap_remove(i);
+    /// Removes the `i`-th solver contact.
+    ///
+    /// The last solver contact is moved to index `i` to replace the removed one.
+    pub fn remove_solver_contact(&self, i: usize) {
+        self.with_ctxt(|ctxt| {
+            if i < ctxt.solver_contacts.len() {
+                ctxt.solver_contacts.swap_remove(i);
             }
-        }
+        });
     }
 
     pub fn solver_contact_point(&self, i: usize) -> Option<RawVector> {
-        unsafe {
-            (*self.solver_contacts)
-                .get(i)
-                .map(|c| c.point.coords.into())
-        }
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.get(i).map(|c| c.point.coords.into()))
+            .flatten()
     }
 
-    pub fn set_solver_contact_point(&mut self, i: usize, pt: &RawVector) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.point = pt.0.into()
+    pub fn set_solver_contact_point(&self, i: usize, pt: &RawVector) {
+        self.with_ctxt(|ctxt| {
+            if let Some(c) = ctxt.solver_contacts.get_mut(i) {
+                c.point = pt.0.into();
             }
-        }
+        });
     }
 
     pub fn solver_contact_dist(&self, i: usize) -> Real {
-        unsafe {
-            (*self.solver_contacts)
-                .get(i)
-                .map(|c| c.dist)
-                .unwrap_or(0.0)
-        }
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.get(i).map(|c| c.dist))
+            .flatten()
+            .unwrap_or(0.0)
     }
 
-    pub fn set_solver_contact_dist(&mut self, i: usize, dist: Real) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.dist = dist
+    pub fn set_solver_contact_dist(&self, i: usize, dist: Real) {
+        self.with_ctxt(|ctxt| {
+            if let Some(c) = ctxt.solver_contacts.get_mut(i) {
+                c.dist = dist;
             }
-        }
+        });
     }
 
     pub fn solver_contact_friction(&self, i: usize) -> Real {
-        unsafe { (*self.solver_contacts)[i].friction }
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.get(i).map(|c| c.friction))
+            .flatten()
+            .unwrap_or(0.0)
     }
 
-    pub fn set_solver_contact_friction(&mut self, i: usize, friction: Real) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.friction = friction
+    pub fn set_solver_contact_friction(&self, i: usize, friction: Real) {
+        self.with_ctxt(|ctxt| {
+            if let Some(c) = ctxt.solver_contacts.get_mut(i) {
+                c.friction = friction;
             }
-        }
+        });
     }
 
     pub fn solver_contact_restitution(&self, i: usize) -> Real {
-        unsafe { (*self.solver_contacts)[i].restitution }
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.get(i).map(|c| c.restitution))
+            .flatten()
+            .unwrap_or(0.0)
     }
 
-    pub fn set_solver_contact_restitution(&mut self, i: usize, restitution: Real) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.restitution = restitution
+    pub fn set_solver_contact_restitution(&self, i: usize, restitution: Real) {
+        self.with_ctxt(|ctxt| {
+            if let Some(c) = ctxt.solver_contacts.get_mut(i) {
+                c.restitution = restitution;
             }
-        }
+        });
     }
 
-    pub fn solver_contact_tangent_velocity(&self, i: usize) -> RawVector {
-        unsafe { (*self.solver_contacts)[i].tangent_velocity.into() }
+    pub fn solver_contact_tangent_velocity(&self, i: usize) -> Option<RawVector> {
+        self.with_ctxt(|ctxt| {
+            ctxt.solver_contacts
+                .get(i)
+                .map(|c| c.tangent_velocity.into())
+        })
+        .flatten()
     }
 
-    pub fn set_solver_contact_tangent_velocity(&mut self, i: usize, vel: &RawVector) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.tangent_velocity = vel.0.into()
+    pub fn set_solver_contact_tangent_velocity(&self, i: usize, vel: &RawVector) {
+        self.with_ctxt(|ctxt| {
+            if let Some(c) = ctxt.solver_contacts.get_mut(i) {
+                c.tangent_velocity = vel.0;
             }
-        }
+        });
+    }
+
+    /// Did the `i`-th solver contact not exist at the previous timestep?
+    pub fn solver_contact_is_new(&self, i: usize) -> bool {
+        self.with_ctxt(|ctxt| ctxt.solver_contacts.get(i).map(|c| c.is_new != 0.0))
+            .flatten()
+            .unwrap_or(false)
+    }
+
+    /// Updates the solver contacts to emulate a one-way platform.
+    ///
+    /// Contacts are only kept if the contact normal in the local-space of the first collider is
+    /// within `allowedAngle` of `allowedLocalN1`. This relies on the manifold user-data, so it must
+    /// not be modified by the hook when using this method.
+    pub fn update_as_oneway_platform(&self, allowedLocalN1: &RawVector, allowedAngle: Real) {
+        self.with_ctxt(|ctxt| ctxt.update_as_oneway_platform(&allowedLocalN1.0, allowedAngle));
     }
 }
-*/
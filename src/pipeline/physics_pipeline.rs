@@ -123,6 +123,8 @@ impl RawPhysicsPipeline {
             &(),
             &(),
         );
+
+        joints.break_joints(&bodies.0, &integrationParameters.0);
     }
 
     pub fn stepWithEvents(
@@ -141,6 +143,7 @@ impl RawPhysicsPipeline {
         hookObject: js_sys::Object,
         hookFilterContactPair: js_sys::Function,
         hookFilterIntersectionPair: js_sys::Function,
+        hookModifySolverContacts: js_sys::Function,
     ) {
         if eventQueue.auto_drain {
             eventQueue.clear();
@@ -150,8 +153,10 @@ impl RawPhysicsPipeline {
             this: hookObject,
             filter_contact_pair: hookFilterContactPair,
             filter_intersection_pair: hookFilterIntersectionPair,
+            modify_solver_contacts: hookModifySolverContacts,
         };
 
+        eventQueue.begin_sleep_events(&bodies.0, &islands.0);
         self.0.step(
             &gravity.0,
             &integrationParameters.0,
@@ -166,5 +171,9 @@ impl RawPhysicsPipeline {
             &hooks,
             &eventQueue.collector,
         );
+
+        let broken_joints = joints.break_joints(&bodies.0, &integrationParameters.0);
+        eventQueue.push_joint_break_events(&joints.0, &broken_joints);
+        eventQueue.collect_sleep_events(&bodies.0, &islands.0);
     }
 }
@@ -0,0 +1,1397 @@
+use super::serialization_pipeline::DeserializableWorld;
+use crate::dynamics::{
+    set_activation_thresholds, JointBreakThreshold, JointBreakThresholds, RawImpulseJointSet,
+    RawIntegrationParameters, RawMultibodyJointSet, RawRigidBodySet,
+};
+use crate::geometry::RawColliderSet;
+use crate::math::RawVector;
+use crate::pipeline::{RawDeserializedWorld, RawSerializationPipeline};
+use rapier::dynamics::{
+    CoefficientCombineRule, GenericJoint, GenericJointBuilder, ImpulseJointSet,
+    IntegrationParameters, IslandManager, JointAxesMask, JointAxis, JointEnabled, LockedAxes,
+    MassProperties, MotorModel, MultibodyJointSet, RigidBody, RigidBodyActivation,
+    RigidBodyAdditionalMassProps, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
+};
+use rapier::geometry::{
+    ActiveCollisionTypes, Collider, ColliderBuilder, ColliderMassProps, ColliderSet,
+    DefaultBroadPhase, NarrowPhase, SharedShape, TriMeshFlags,
+};
+use rapier::math::{AngVector, Isometry, Point, Real, Rotation, Vector, DIM};
+use rapier::parry::shape::ShapeType;
+use rapier::pipeline::{ActiveEvents, ActiveHooks};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "dim2")]
+use na::DVector;
+#[cfg(feature = "dim3")]
+use {na::DMatrix, rapier::parry::shape::HeightFieldFlags};
+
+/// Version of the scene format.
+///
+/// Scenes don’t depend on the internal data-structures of the physics engine, so this only
+/// needs to be incremented when the layout of `Scene` changes in a non-backward-compatible way.
+const SCENE_FORMAT_VERSION: u32 = 1;
+
+#[cfg(feature = "dim2")]
+const JOINT_AXES: [JointAxis; 3] = [JointAxis::LinX, JointAxis::LinY, JointAxis::AngX];
+#[cfg(feature = "dim3")]
+const JOINT_AXES: [JointAxis; 6] = [
+    JointAxis::LinX,
+    JointAxis::LinY,
+    JointAxis::LinZ,
+    JointAxis::AngX,
+    JointAxis::AngY,
+    JointAxis::AngZ,
+];
+
+/// A rotation angle in 2D.
+#[cfg(feature = "dim2")]
+type SceneRotation = Real;
+/// A unit quaternion `[x, y, z, w]` in 3D.
+#[cfg(feature = "dim3")]
+type SceneRotation = [Real; 4];
+
+#[cfg(feature = "dim2")]
+fn scene_rotation(rot: &Rotation<Real>) -> SceneRotation {
+    rot.angle()
+}
+
+#[cfg(feature = "dim3")]
+fn scene_rotation(rot: &Rotation<Real>) -> SceneRotation {
+    let coords = rot.coords;
+    [coords.x, coords.y, coords.z, coords.w]
+}
+
+#[cfg(feature = "dim2")]
+fn rotation_from_scene(angle: SceneRotation) -> Result<Rotation<Real>, JsError> {
+    Ok(Rotation::new(angle))
+}
+
+#[cfg(feature = "dim3")]
+fn rotation_from_scene([x, y, z, w]: SceneRotation) -> Result<Rotation<Real>, JsError> {
+    na::Unit::try_new(na::Quaternion::new(w, x, y, z), 0.0)
+        .ok_or_else(|| scene_error("rotations must not be zero quaternions."))
+}
+
+fn scene_error(msg: impl std::fmt::Display) -> JsError {
+    JsError::new(&format!("Invalid scene: {}", msg))
+}
+
+/// Checks that all the vertex indices of a shape are smaller than its number of vertices.
+fn check_indices<const N: usize>(
+    indices: &[[u32; N]],
+    vertices: &[Point<Real>],
+    shape: &str,
+) -> Result<(), JsError> {
+    match indices
+        .iter()
+        .flatten()
+        .find(|i| **i as usize >= vertices.len())
+    {
+        Some(i) => Err(scene_error(format!(
+            "{} vertex index {} out of bounds ({} vertices).",
+            shape,
+            i,
+            vertices.len()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// A physics world described with the same parameters as the ones given to the rigid-body,
+/// collider, and joint builders.
+///
+/// Rigid-bodies and joints refer to each other by their index in the `bodies` array instead of
+/// by their handles.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Scene {
+    version: u32,
+    dim: u8,
+    #[serde(default)]
+    gravity: Vector<Real>,
+    #[serde(default)]
+    integration_parameters: SceneIntegrationParameters,
+    #[serde(default)]
+    bodies: Vec<SceneRigidBody>,
+    #[serde(default)]
+    colliders: Vec<SceneCollider>,
+    #[serde(default)]
+    impulse_joints: Vec<SceneImpulseJoint>,
+    #[serde(default)]
+    multibody_joints: Vec<SceneMultibodyJoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SceneIntegrationParameters {
+    dt: Real,
+    min_ccd_dt: Real,
+    contact_natural_frequency: Real,
+    contact_damping_ratio: Real,
+    joint_natural_frequency: Real,
+    joint_damping_ratio: Real,
+    warmstart_coefficient: Real,
+    length_unit: Real,
+    normalized_allowed_linear_error: Real,
+    normalized_max_corrective_velocity: Real,
+    normalized_prediction_distance: Real,
+    num_solver_iterations: usize,
+    num_internal_pgs_iterations: usize,
+    num_internal_stabilization_iterations: usize,
+    min_island_size: usize,
+    max_ccd_substeps: usize,
+}
+
+impl Default for SceneIntegrationParameters {
+    fn default() -> Self {
+        Self::new(&IntegrationParameters::default())
+    }
+}
+
+impl SceneIntegrationParameters {
+    fn new(params: &IntegrationParameters) -> Self {
+        Self {
+            dt: params.dt,
+            min_ccd_dt: params.min_ccd_dt,
+            contact_natural_frequency: params.contact_natural_frequency,
+            contact_damping_ratio: params.contact_damping_ratio,
+            joint_natural_frequency: params.joint_natural_frequency,
+            joint_damping_ratio: params.joint_damping_ratio,
+            warmstart_coefficient: params.warmstart_coefficient,
+            length_unit: params.length_unit,
+            normalized_allowed_linear_error: params.normalized_allowed_linear_error,
+            normalized_max_corrective_velocity: params.normalized_max_corrective_velocity,
+            normalized_prediction_distance: params.normalized_prediction_distance,
+            num_solver_iterations: params.num_solver_iterations,
+            num_internal_pgs_iterations: params.num_internal_pgs_iterations,
+            num_internal_stabilization_iterations: params.num_internal_stabilization_iterations,
+            min_island_size: params.min_island_size,
+            max_ccd_substeps: params.max_ccd_substeps,
+        }
+    }
+
+    fn build(&self) -> IntegrationParameters {
+        IntegrationParameters {
+            dt: self.dt,
+            min_ccd_dt: self.min_ccd_dt,
+            contact_natural_frequency: self.contact_natural_frequency,
+            contact_damping_ratio: self.contact_damping_ratio,
+            joint_natural_frequency: self.joint_natural_frequency,
+            joint_damping_ratio: self.joint_damping_ratio,
+            warmstart_coefficient: self.warmstart_coefficient,
+            length_unit: self.length_unit,
+            normalized_allowed_linear_error: self.normalized_allowed_linear_error,
+            normalized_max_corrective_velocity: self.normalized_max_corrective_velocity,
+            normalized_prediction_distance: self.normalized_prediction_distance,
+            num_solver_iterations: self.num_solver_iterations,
+            num_internal_pgs_iterations: self.num_internal_pgs_iterations,
+            num_internal_stabilization_iterations: self.num_internal_stabilization_iterations,
+            min_island_size: self.min_island_size,
+            max_ccd_substeps: self.max_ccd_substeps,
+            // The friction model isn’t configurable from JS, so it keeps its default value.
+            #[cfg(feature = "dim3")]
+            friction_model: IntegrationParameters::default().friction_model,
+        }
+    }
+}
+
+/// The parameters of `RawRigidBodySet.createRigidBody`.
+#[derive(Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SceneRigidBody {
+    status: RigidBodyType,
+    enabled: bool,
+    translation: Vector<Real>,
+    rotation: SceneRotation,
+    gravity_scale: Real,
+    mass: Real,
+    mass_only: bool,
+    center_of_mass: Point<Real>,
+    principal_angular_inertia: AngVector<Real>,
+    #[cfg(feature = "dim3")]
+    angular_inertia_local_frame: SceneRotation,
+    linvel: Vector<Real>,
+    angvel: AngVector<Real>,
+    translations_enabled: [bool; DIM],
+    #[cfg(feature = "dim2")]
+    rotations_enabled: bool,
+    #[cfg(feature = "dim3")]
+    rotations_enabled: [bool; 3],
+    linear_damping: Real,
+    angular_damping: Real,
+    can_sleep: bool,
+    sleeping: bool,
+    normalized_linear_sleep_threshold: Real,
+    angular_sleep_threshold: Real,
+    time_until_sleep: Real,
+    ccd_enabled: bool,
+    soft_ccd_prediction: Real,
+    dominance_group: i8,
+    additional_solver_iterations: usize,
+}
+
+impl Default for SceneRigidBody {
+    fn default() -> Self {
+        Self {
+            status: RigidBodyType::Dynamic,
+            enabled: true,
+            translation: Vector::zeros(),
+            rotation: scene_rotation(&Rotation::identity()),
+            gravity_scale: 1.0,
+            mass: 0.0,
+            mass_only: false,
+            center_of_mass: Point::origin(),
+            principal_angular_inertia: na::zero(),
+            #[cfg(feature = "dim3")]
+            angular_inertia_local_frame: scene_rotation(&Rotation::identity()),
+            linvel: Vector::zeros(),
+            angvel: na::zero(),
+            translations_enabled: [true; DIM],
+            #[cfg(feature = "dim2")]
+            rotations_enabled: true,
+            #[cfg(feature = "dim3")]
+            rotations_enabled: [true; 3],
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            can_sleep: true,
+            sleeping: false,
+            normalized_linear_sleep_threshold:
+                RigidBodyActivation::default_normalized_linear_threshold(),
+            angular_sleep_threshold: RigidBodyActivation::default_angular_threshold(),
+            time_until_sleep: RigidBodyActivation::default_time_until_sleep(),
+            ccd_enabled: false,
+            soft_ccd_prediction: 0.0,
+            dominance_group: 0,
+            additional_solver_iterations: 0,
+        }
+    }
+}
+
+/// The explicit mass-properties or density given to a collider.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all_fields = "camelCase")]
+enum SceneMassProps {
+    Density {
+        density: Real,
+    },
+    Mass {
+        mass: Real,
+    },
+    MassProps {
+        mass: Real,
+        center_of_mass: Point<Real>,
+        principal_angular_inertia: AngVector<Real>,
+        #[cfg(feature = "dim3")]
+        angular_inertia_local_frame: SceneRotation,
+    },
+}
+
+impl Default for SceneMassProps {
+    fn default() -> Self {
+        Self::Density { density: 1.0 }
+    }
+}
+
+/// The shape of a collider, tagged with the name of its `ShapeType`.
+///
+/// The fields are the parameters of the corresponding `RawShape` constructor.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+enum SceneShape {
+    Ball {
+        radius: Real,
+    },
+    Cuboid {
+        half_extents: Vector<Real>,
+    },
+    RoundCuboid {
+        half_extents: Vector<Real>,
+        border_radius: Real,
+    },
+    Capsule {
+        half_height: Real,
+        radius: Real,
+    },
+    Segment {
+        a: Point<Real>,
+        b: Point<Real>,
+    },
+    Triangle {
+        a: Point<Real>,
+        b: Point<Real>,
+        c: Point<Real>,
+    },
+    RoundTriangle {
+        a: Point<Real>,
+        b: Point<Real>,
+        c: Point<Real>,
+        border_radius: Real,
+    },
+    Polyline {
+        vertices: Vec<Point<Real>>,
+        #[serde(default)]
+        indices: Vec<[u32; 2]>,
+    },
+    TriMesh {
+        vertices: Vec<Point<Real>>,
+        indices: Vec<[u32; 3]>,
+        #[serde(default)]
+        flags: u16,
+    },
+    #[cfg(feature = "dim2")]
+    HeightField {
+        heights: Vec<Real>,
+        scale: Vector<Real>,
+    },
+    /// The heights are given in column-major order, with `nrows + 1` rows and `ncols + 1`
+    /// columns.
+    #[cfg(feature = "dim3")]
+    HeightField {
+        nrows: usize,
+        ncols: usize,
+        heights: Vec<Real>,
+        scale: Vector<Real>,
+        #[serde(default)]
+        flags: u8,
+    },
+    HalfSpace {
+        normal: Vector<Real>,
+    },
+    Voxels {
+        voxel_size: Vector<Real>,
+        grid_coords: Vec<Point<i32>>,
+    },
+    #[cfg(feature = "dim2")]
+    ConvexPolygon {
+        vertices: Vec<Point<Real>>,
+    },
+    #[cfg(feature = "dim2")]
+    RoundConvexPolygon {
+        vertices: Vec<Point<Real>>,
+        border_radius: Real,
+    },
+    #[cfg(feature = "dim3")]
+    ConvexPolyhedron {
+        vertices: Vec<Point<Real>>,
+        indices: Vec<[u32; 3]>,
+    },
+    #[cfg(feature = "dim3")]
+    RoundConvexPolyhedron {
+        vertices: Vec<Point<Real>>,
+        indices: Vec<[u32; 3]>,
+        border_radius: Real,
+    },
+    #[cfg(feature = "dim3")]
+    Cylinder {
+        half_height: Real,
+        radius: Real,
+    },
+    #[cfg(feature = "dim3")]
+    RoundCylinder {
+        half_height: Real,
+        radius: Real,
+        border_radius: Real,
+    },
+    #[cfg(feature = "dim3")]
+    Cone {
+        half_height: Real,
+        radius: Real,
+    },
+    #[cfg(feature = "dim3")]
+    RoundCone {
+        half_height: Real,
+        radius: Real,
+        border_radius: Real,
+    },
+}
+
+impl SceneShape {
+    fn new(shape: &SharedShape) -> Result<Self, JsError> {
+        let unsupported = || JsError::new("Cannot export a scene with compound or custom shapes.");
+        let result = match shape.shape_type() {
+            ShapeType::Ball => {
+                let s = shape.as_ball().ok_or_else(unsupported)?;
+                Self::Ball { radius: s.radius }
+            }
+            ShapeType::Cuboid => {
+                let s = shape.as_cuboid().ok_or_else(unsupported)?;
+                Self::Cuboid {
+                    half_extents: s.half_extents,
+                }
+            }
+            ShapeType::RoundCuboid => {
+                let s = shape.as_round_cuboid().ok_or_else(unsupported)?;
+                Self::RoundCuboid {
+                    half_extents: s.inner_shape.half_extents,
+                    border_radius: s.border_radius,
+                }
+            }
+            ShapeType::Capsule => {
+                let s = shape.as_capsule().ok_or_else(unsupported)?;
+                Self::Capsule {
+                    half_height: s.half_height(),
+                    radius: s.radius,
+                }
+            }
+            ShapeType::Segment => {
+                let s = shape.as_segment().ok_or_else(unsupported)?;
+                Self::Segment { a: s.a, b: s.b }
+            }
+            ShapeType::Triangle => {
+                let s = shape.as_triangle().ok_or_else(unsupported)?;
+                Self::Triangle {
+                    a: s.a,
+                    b: s.b,
+                    c: s.c,
+                }
+            }
+            ShapeType::RoundTriangle => {
+                let s = shape.as_round_triangle().ok_or_else(unsupported)?;
+                Self::RoundTriangle {
+                    a: s.inner_shape.a,
+                    b: s.inner_shape.b,
+                    c: s.inner_shape.c,
+                    border_radius: s.border_radius,
+                }
+            }
+            ShapeType::Polyline => {
+                let s = shape.as_polyline().ok_or_else(unsupported)?;
+                Self::Polyline {
+                    vertices: s.vertices().to_vec(),
+                    indices: s.indices().to_vec(),
+                }
+            }
+            ShapeType::TriMesh => {
+                let s = shape.as_trimesh().ok_or_else(unsupported)?;
+                Self::TriMesh {
+                    vertices: s.vertices().to_vec(),
+                    indices: s.indices().to_vec(),
+                    flags: s.flags().bits(),
+                }
+            }
+            #[cfg(feature = "dim2")]
+            ShapeType::HeightField => {
+                let s = shape.as_heightfield().ok_or_else(unsupported)?;
+                Self::HeightField {
+                    heights: s.heights().as_slice().to_vec(),
+                    scale: *s.scale(),
+                }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::HeightField => {
+                let s = shape.as_heightfield().ok_or_else(unsupported)?;
+                Self::HeightField {
+                    nrows: s.nrows(),
+                    ncols: s.ncols(),
+                    heights: s.heights().as_slice().to_vec(),
+                    scale: *s.scale(),
+                    flags: s.flags().bits(),
+                }
+            }
+            ShapeType::HalfSpace => {
+                let s = shape.as_halfspace().ok_or_else(unsupported)?;
+                Self::HalfSpace {
+                    normal: s.normal.into_inner(),
+                }
+            }
+            ShapeType::Voxels => {
+                let s = shape.as_voxels().ok_or_else(unsupported)?;
+                Self::Voxels {
+                    voxel_size: s.voxel_size(),
+                    grid_coords: s
+                        .voxels()
+                        .filter_map(|vox| (!vox.state.is_empty()).then_some(vox.grid_coords))
+                        .collect(),
+                }
+            }
+            #[cfg(feature = "dim2")]
+            ShapeType::ConvexPolygon => {
+                let s = shape.as_convex_polygon().ok_or_else(unsupported)?;
+                Self::ConvexPolygon {
+                    vertices: s.points().to_vec(),
+                }
+            }
+            #[cfg(feature = "dim2")]
+            ShapeType::RoundConvexPolygon => {
+                let s = shape.as_round_convex_polygon().ok_or_else(unsupported)?;
+                Self::RoundConvexPolygon {
+                    vertices: s.inner_shape.points().to_vec(),
+                    border_radius: s.border_radius,
+                }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::ConvexPolyhedron => {
+                let s = shape.as_convex_polyhedron().ok_or_else(unsupported)?;
+                let (vertices, indices) = s.to_trimesh();
+                Self::ConvexPolyhedron { vertices, indices }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::RoundConvexPolyhedron => {
+                let s = shape.as_round_convex_polyhedron().ok_or_else(unsupported)?;
+                let (vertices, indices) = s.inner_shape.to_trimesh();
+                Self::RoundConvexPolyhedron {
+                    vertices,
+                    indices,
+                    border_radius: s.border_radius,
+                }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::Cylinder => {
+                let s = shape.as_cylinder().ok_or_else(unsupported)?;
+                Self::Cylinder {
+                    half_height: s.half_height,
+                    radius: s.radius,
+                }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::RoundCylinder => {
+                let s = shape.as_round_cylinder().ok_or_else(unsupported)?;
+                Self::RoundCylinder {
+                    half_height: s.inner_shape.half_height,
+                    radius: s.inner_shape.radius,
+                    border_radius: s.border_radius,
+                }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::Cone => {
+                let s = shape.as_cone().ok_or_else(unsupported)?;
+                Self::Cone {
+                    half_height: s.half_height,
+                    radius: s.radius,
+                }
+            }
+            #[cfg(feature = "dim3")]
+            ShapeType::RoundCone => {
+                let s = shape.as_round_cone().ok_or_else(unsupported)?;
+                Self::RoundCone {
+                    half_height: s.inner_shape.half_height,
+                    radius: s.inner_shape.radius,
+                    border_radius: s.border_radius,
+                }
+            }
+            _ => return Err(unsupported()),
+        };
+
+        Ok(result)
+    }
+
+    fn build(self) -> Result<SharedShape, JsError> {
+        let result = match self {
+            Self::Ball { radius } => SharedShape::ball(radius),
+            #[cfg(feature = "dim2")]
+            Self::Cuboid { half_extents } => SharedShape::cuboid(half_extents.x, half_extents.y),
+            #[cfg(feature = "dim3")]
+            Self::Cuboid { half_extents } => {
+                SharedShape::cuboid(half_extents.x, half_extents.y, half_extents.z)
+            }
+            #[cfg(feature = "dim2")]
+            Self::RoundCuboid {
+                half_extents,
+                border_radius,
+            } => SharedShape::round_cuboid(half_extents.x, half_extents.y, border_radius),
+            #[cfg(feature = "dim3")]
+            Self::RoundCuboid {
+                half_extents,
+                border_radius,
+            } => SharedShape::round_cuboid(
+                half_extents.x,
+                half_extents.y,
+                half_extents.z,
+                border_radius,
+            ),
+            Self::Capsule {
+                half_height,
+                radius,
+            } => {
+                let p2 = Point::from(Vector::y() * half_height);
+                SharedShape::capsule(-p2, p2, radius)
+            }
+            Self::Segment { a, b } => SharedShape::segment(a, b),
+            Self::Triangle { a, b, c } => SharedShape::triangle(a, b, c),
+            Self::RoundTriangle {
+                a,
+                b,
+                c,
+                border_radius,
+            } => SharedShape::round_triangle(a, b, c, border_radius),
+            Self::Polyline { vertices, indices } => {
+                check_indices(&indices, &vertices, "polyline")?;
+                let indices = (!indices.is_empty()).then_some(indices);
+                SharedShape::polyline(vertices, indices)
+            }
+            Self::TriMesh {
+                vertices,
+                indices,
+                flags,
+            } => {
+                check_indices(&indices, &vertices, "triangle mesh")?;
+                let flags = TriMeshFlags::from_bits(flags).unwrap_or_default();
+                SharedShape::trimesh_with_flags(vertices, indices, flags).map_err(scene_error)?
+            }
+            #[cfg(feature = "dim2")]
+            Self::HeightField { heights, scale } => {
+                if heights.len() < 2 {
+                    return Err(scene_error("heightfields need at least two heights."));
+                }
+                SharedShape::heightfield(DVector::from_vec(heights), scale)
+            }
+            #[cfg(feature = "dim3")]
+            Self::HeightField {
+                nrows,
+                ncols,
+                heights,
+                scale,
+                flags,
+            } => {
+                if nrows == 0 || ncols == 0 {
+                    return Err(scene_error(
+                        "heightfields need at least one row and column.",
+                    ));
+                }
+                if heights.len() != (nrows + 1) * (ncols + 1) {
+                    return Err(scene_error("invalid number of heightfield heights."));
+                }
+                let flags = HeightFieldFlags::from_bits(flags).unwrap_or_default();
+                let heights = DMatrix::from_vec(nrows + 1, ncols + 1, heights);
+                SharedShape::heightfield_with_flags(heights, scale, flags)
+            }
+            Self::HalfSpace { normal } => SharedShape::halfspace(
+                na::Unit::try_new(normal, 0.0)
+                    .ok_or_else(|| scene_error("half-space normals must not be zero."))?,
+            ),
+            Self::Voxels {
+                voxel_size,
+                grid_coords,
+            } => SharedShape::voxels(voxel_size, &grid_coords),
+            #[cfg(feature = "dim2")]
+            Self::ConvexPolygon { vertices } => SharedShape::convex_polyline(vertices)
+                .ok_or_else(|| scene_error("degenerate convex polygon."))?,
+            #[cfg(feature = "dim2")]
+            Self::RoundConvexPolygon {
+                vertices,
+                border_radius,
+            } => SharedShape::round_convex_polyline(vertices, border_radius)
+                .ok_or_else(|| scene_error("degenerate convex polygon."))?,
+            #[cfg(feature = "dim3")]
+            Self::ConvexPolyhedron { vertices, indices } => {
+                check_indices(&indices, &vertices, "convex polyhedron")?;
+                SharedShape::convex_mesh(vertices, &indices)
+                    .ok_or_else(|| scene_error("degenerate convex polyhedron."))?
+            }
+            #[cfg(feature = "dim3")]
+            Self::RoundConvexPolyhedron {
+                vertices,
+                indices,
+                border_radius,
+            } => {
+                check_indices(&indices, &vertices, "convex polyhedron")?;
+                SharedShape::round_convex_mesh(vertices, &indices, border_radius)
+                    .ok_or_else(|| scene_error("degenerate convex polyhedron."))?
+            }
+            #[cfg(feature = "dim3")]
+            Self::Cylinder {
+                half_height,
+                radius,
+            } => SharedShape::cylinder(half_height, radius),
+            #[cfg(feature = "dim3")]
+            Self::RoundCylinder {
+                half_height,
+                radius,
+                border_radius,
+            } => SharedShape::round_cylinder(half_height, radius, border_radius),
+            #[cfg(feature = "dim3")]
+            Self::Cone {
+                half_height,
+                radius,
+            } => SharedShape::cone(half_height, radius),
+            #[cfg(feature = "dim3")]
+            Self::RoundCone {
+                half_height,
+                radius,
+                border_radius,
+            } => SharedShape::round_cone(half_height, radius, border_radius),
+        };
+
+        Ok(result)
+    }
+}
+
+/// The parameters of `RawColliderSet.createCollider`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SceneCollider {
+    shape: SceneShape,
+    /// The index of the parent rigid-body in `Scene::bodies`, if any.
+    #[serde(default)]
+    parent: Option<usize>,
+    #[serde(flatten)]
+    props: SceneColliderProps,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SceneColliderProps {
+    enabled: bool,
+    /// The position relative to the parent rigid-body, if any.
+    translation: Vector<Real>,
+    rotation: SceneRotation,
+    mass_props: SceneMassProps,
+    friction: Real,
+    restitution: Real,
+    friction_combine_rule: CoefficientCombineRule,
+    restitution_combine_rule: CoefficientCombineRule,
+    is_sensor: bool,
+    collision_groups: u32,
+    solver_groups: u32,
+    active_collision_types: u16,
+    active_hooks: u32,
+    active_events: u32,
+    contact_force_event_threshold: Real,
+    contact_skin: Real,
+}
+
+impl Default for SceneColliderProps {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            translation: Vector::zeros(),
+            rotation: scene_rotation(&Rotation::identity()),
+            mass_props: SceneMassProps::default(),
+            friction: 0.5,
+            restitution: 0.0,
+            friction_combine_rule: CoefficientCombineRule::Average,
+            restitution_combine_rule: CoefficientCombineRule::Average,
+            is_sensor: false,
+            collision_groups: 0xffff_ffff,
+            solver_groups: 0xffff_ffff,
+            active_collision_types: ActiveCollisionTypes::default().bits(),
+            active_hooks: 0,
+            active_events: 0,
+            contact_force_event_threshold: 0.0,
+            contact_skin: 0.0,
+        }
+    }
+}
+
+/// A joint frame, relative to the rigid-body it is attached to.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct SceneFrame {
+    translation: Vector<Real>,
+    rotation: SceneRotation,
+}
+
+impl Default for SceneFrame {
+    fn default() -> Self {
+        Self::new(&Isometry::identity())
+    }
+}
+
+impl SceneFrame {
+    fn new(pos: &Isometry<Real>) -> Self {
+        Self {
+            translation: pos.translation.vector,
+            rotation: scene_rotation(&pos.rotation),
+        }
+    }
+
+    fn build(&self) -> Result<Isometry<Real>, JsError> {
+        Ok(Isometry::from_parts(
+            self.translation.into(),
+            rotation_from_scene(self.rotation)?,
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SceneJointLimits {
+    axis: JointAxis,
+    min: Real,
+    max: Real,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SceneJointMotor {
+    axis: JointAxis,
+    #[serde(default)]
+    target_pos: Real,
+    #[serde(default)]
+    target_vel: Real,
+    #[serde(default)]
+    stiffness: Real,
+    #[serde(default)]
+    damping: Real,
+    #[serde(default = "scene_default_max_force")]
+    max_force: Real,
+    #[serde(default)]
+    model: MotorModel,
+}
+
+fn scene_default_max_force() -> Real {
+    Real::MAX
+}
+
+/// The description of a generic joint, as given to the joint builders.
+#[derive(Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct SceneJointData {
+    frame1: SceneFrame,
+    frame2: SceneFrame,
+    locked_axes: Vec<JointAxis>,
+    limits: Vec<SceneJointLimits>,
+    motors: Vec<SceneJointMotor>,
+    coupled_axes: Vec<JointAxis>,
+    contacts_enabled: bool,
+    enabled: bool,
+}
+
+impl Default for SceneJointData {
+    fn default() -> Self {
+        Self::new(&GenericJoint::default())
+    }
+}
+
+impl SceneJointData {
+    fn new(joint: &GenericJoint) -> Self {
+        let axes = |mask: JointAxesMask| {
+            JOINT_AXES
+                .iter()
+                .copied()
+                .filter(|axis| mask.contains((*axis).into()))
+                .collect()
+        };
+        let limits = JOINT_AXES
+            .iter()
+            .copied()
+            .filter_map(|axis| {
+                joint.limits(axis).map(|limits| SceneJointLimits {
+                    axis,
+                    min: limits.min,
+                    max: limits.max,
+                })
+            })
+            .collect();
+        let motors = JOINT_AXES
+            .iter()
+            .copied()
+            .filter_map(|axis| {
+                joint.motor(axis).map(|motor| SceneJointMotor {
+                    axis,
+                    target_pos: motor.target_pos,
+                    target_vel: motor.target_vel,
+                    stiffness: motor.stiffness,
+                    damping: motor.damping,
+                    max_force: motor.max_force,
+                    model: motor.model,
+                })
+            })
+            .collect();
+
+        Self {
+            frame1: SceneFrame::new(&joint.local_frame1),
+            frame2: SceneFrame::new(&joint.local_frame2),
+            locked_axes: axes(joint.locked_axes),
+            limits,
+            motors,
+            coupled_axes: axes(joint.coupled_axes),
+            contacts_enabled: joint.contacts_enabled,
+            enabled: joint.enabled != JointEnabled::Disabled,
+        }
+    }
+
+    fn build(&self) -> Result<GenericJoint, JsError> {
+        let axes = |axes: &[JointAxis]| {
+            axes.iter()
+                .fold(JointAxesMask::empty(), |mask, axis| mask | (*axis).into())
+        };
+        let mut builder = GenericJointBuilder::new(axes(&self.locked_axes))
+            .local_frame1(self.frame1.build()?)
+            .local_frame2(self.frame2.build()?)
+            .coupled_axes(axes(&self.coupled_axes))
+            .contacts_enabled(self.contacts_enabled);
+
+        for limits in &self.limits {
+            builder = builder.limits(limits.axis, [limits.min, limits.max]);
+        }
+
+        for motor in &self.motors {
+            builder = builder
+                .set_motor(
+                    motor.axis,
+                    motor.target_pos,
+                    motor.target_vel,
+                    motor.stiffness,
+                    motor.damping,
+                )
+                .motor_max_force(motor.axis, motor.max_force)
+                .motor_model(motor.axis, motor.model);
+        }
+
+        let mut joint = builder.build();
+        joint.set_enabled(self.enabled);
+        Ok(joint)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SceneImpulseJoint {
+    /// The index of the first attached rigid-body in `Scene::bodies`.
+    body1: usize,
+    /// The index of the second attached rigid-body in `Scene::bodies`.
+    body2: usize,
+    /// The force above which the joint breaks, if it is breakable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    break_force: Option<Real>,
+    /// The torque above which the joint breaks, if it is breakable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    break_torque: Option<Real>,
+    #[serde(flatten)]
+    data: SceneJointData,
+}
+
+impl SceneImpulseJoint {
+    /// The break threshold of this joint, or `None` if it isn’t breakable.
+    fn break_threshold(&self) -> Option<JointBreakThreshold> {
+        if self.break_force.is_none() && self.break_torque.is_none() {
+            return None;
+        }
+
+        Some(JointBreakThreshold {
+            max_force: self.break_force.unwrap_or(Real::INFINITY),
+            max_torque: self.break_torque.unwrap_or(Real::INFINITY),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SceneMultibodyJoint {
+    /// The index of the parent rigid-body in `Scene::bodies`.
+    body1: usize,
+    /// The index of the child rigid-body in `Scene::bodies`.
+    body2: usize,
+    #[serde(default)]
+    kinematic: bool,
+    #[serde(flatten)]
+    data: SceneJointData,
+}
+
+fn scene_rigid_body(bodies: &RigidBodySet, handle: RigidBodyHandle) -> SceneRigidBody {
+    let rb = &bodies[handle];
+    let pos = rb.position();
+    let locked = rb.locked_axes();
+    // `RigidBodyBuilder::can_sleep(false)` disables sleeping with negative thresholds.
+    let can_sleep = rb.activation().normalized_linear_threshold >= 0.0;
+    let mut result = SceneRigidBody {
+        status: rb.body_type(),
+        enabled: rb.is_enabled(),
+        translation: pos.translation.vector,
+        rotation: scene_rotation(&pos.rotation),
+        gravity_scale: rb.gravity_scale(),
+        linvel: *rb.linvel(),
+        #[cfg(feature = "dim2")]
+        angvel: rb.angvel(),
+        #[cfg(feature = "dim3")]
+        angvel: *rb.angvel(),
+        #[cfg(feature = "dim2")]
+        translations_enabled: [
+            !locked.contains(LockedAxes::TRANSLATION_LOCKED_X),
+            !locked.contains(LockedAxes::TRANSLATION_LOCKED_Y),
+        ],
+        #[cfg(feature = "dim3")]
+        translations_enabled: [
+            !locked.contains(LockedAxes::TRANSLATION_LOCKED_X),
+            !locked.contains(LockedAxes::TRANSLATION_LOCKED_Y),
+            !locked.contains(LockedAxes::TRANSLATION_LOCKED_Z),
+        ],
+        #[cfg(feature = "dim2")]
+        rotations_enabled: !locked.intersects(LockedAxes::ROTATION_LOCKED),
+        #[cfg(feature = "dim3")]
+        rotations_enabled: [
+            !locked.contains(LockedAxes::ROTATION_LOCKED_X),
+            !locked.contains(LockedAxes::ROTATION_LOCKED_Y),
+            !locked.contains(LockedAxes::ROTATION_LOCKED_Z),
+        ],
+        linear_damping: rb.linear_damping(),
+        angular_damping: rb.angular_damping(),
+        can_sleep,
+        sleeping: rb.is_sleeping(),
+        time_until_sleep: rb.activation().time_until_sleep,
+        ccd_enabled: rb.is_ccd_enabled(),
+        soft_ccd_prediction: rb.soft_ccd_prediction(),
+        dominance_group: rb.dominance_group(),
+        additional_solver_iterations: rb.additional_solver_iterations(),
+        ..SceneRigidBody::default()
+    };
+
+    match rb.mass_properties().additional_local_mprops.as_deref() {
+        Some(RigidBodyAdditionalMassProps::Mass(mass)) => {
+            result.mass = *mass;
+            result.mass_only = true;
+        }
+        Some(RigidBodyAdditionalMassProps::MassProps(mprops)) => {
+            result.mass = mprops.mass();
+            result.center_of_mass = mprops.local_com;
+            result.principal_angular_inertia = mprops.principal_inertia();
+            #[cfg(feature = "dim3")]
+            {
+                result.angular_inertia_local_frame =
+                    scene_rotation(&mprops.principal_inertia_local_frame);
+            }
+        }
+        None => {}
+    }
+
+    if can_sleep {
+        result.normalized_linear_sleep_threshold = rb.activation().normalized_linear_threshold;
+        result.angular_sleep_threshold = rb.activation().angular_threshold;
+    }
+
+    result
+}
+
+fn build_rigid_body(body: &SceneRigidBody) -> Result<RigidBody, JsError> {
+    let pos = Isometry::from_parts(body.translation.into(), rotation_from_scene(body.rotation)?);
+    #[cfg(feature = "dim2")]
+    let [tx, ty] = body.translations_enabled;
+    #[cfg(feature = "dim3")]
+    let [tx, ty, tz] = body.translations_enabled;
+
+    let mut builder = RigidBodyBuilder::new(body.status)
+        .enabled(body.enabled)
+        .pose(pos)
+        .gravity_scale(body.gravity_scale)
+        .linvel(body.linvel)
+        .angvel(body.angvel)
+        .linear_damping(body.linear_damping)
+        .angular_damping(body.angular_damping)
+        .can_sleep(body.can_sleep)
+        .sleeping(body.sleeping)
+        .ccd_enabled(body.ccd_enabled)
+        .dominance_group(body.dominance_group)
+        .additional_solver_iterations(body.additional_solver_iterations)
+        .soft_ccd_prediction(body.soft_ccd_prediction);
+
+    #[cfg(feature = "dim2")]
+    {
+        builder = builder.enabled_translations(tx, ty);
+        if !body.rotations_enabled {
+            builder = builder.lock_rotations();
+        }
+    }
+    #[cfg(feature = "dim3")]
+    {
+        let [rx, ry, rz] = body.rotations_enabled;
+        builder = builder
+            .enabled_translations(tx, ty, tz)
+            .enabled_rotations(rx, ry, rz);
+    }
+
+    builder = if body.mass_only {
+        builder.additional_mass(body.mass)
+    } else {
+        #[cfg(feature = "dim2")]
+        let props = MassProperties::new(
+            body.center_of_mass,
+            body.mass,
+            body.principal_angular_inertia,
+        );
+        #[cfg(feature = "dim3")]
+        let props = MassProperties::with_principal_inertia_frame(
+            body.center_of_mass,
+            body.mass,
+            body.principal_angular_inertia,
+            rotation_from_scene(body.angular_inertia_local_frame)?,
+        );
+        builder.additional_mass_properties(props)
+    };
+
+    let mut rb = builder.build();
+    set_activation_thresholds(
+        &mut rb,
+        body.can_sleep,
+        body.normalized_linear_sleep_threshold,
+        body.angular_sleep_threshold,
+        body.time_until_sleep,
+    );
+    Ok(rb)
+}
+
+/// The simplest mass-properties description reproducing the mass-properties of the collider.
+///
+/// The mass-properties mode the collider was built with isn’t accessible, so this checks the
+/// density and mass modes first, and falls back to explicit mass-properties.
+fn scene_mass_props(co: &Collider) -> SceneMassProps {
+    let mprops = co.mass_properties();
+    let density = co.density();
+    let mass = co.mass();
+
+    if ColliderMassProps::Density(density).mass_properties(co.shape()) == mprops {
+        SceneMassProps::Density { density }
+    } else if ColliderMassProps::Mass(mass).mass_properties(co.shape()) == mprops {
+        SceneMassProps::Mass { mass }
+    } else {
+        SceneMassProps::MassProps {
+            mass: mprops.mass(),
+            center_of_mass: mprops.local_com,
+            principal_angular_inertia: mprops.principal_inertia(),
+            #[cfg(feature = "dim3")]
+            angular_inertia_local_frame: scene_rotation(&mprops.principal_inertia_local_frame),
+        }
+    }
+}
+
+fn build_collider(
+    shape: SharedShape,
+    props: &SceneColliderProps,
+) -> Result<ColliderBuilder, JsError> {
+    let pos = Isometry::from_parts(
+        props.translation.into(),
+        rotation_from_scene(props.rotation)?,
+    );
+    let builder = ColliderBuilder::new(shape)
+        .enabled(props.enabled)
+        .position(pos)
+        .friction(props.friction)
+        .restitution(props.restitution)
+        .collision_groups(crate::geometry::unpack_interaction_groups(
+            props.collision_groups,
+        ))
+        .solver_groups(crate::geometry::unpack_interaction_groups(
+            props.solver_groups,
+        ))
+        .active_hooks(ActiveHooks::from_bits(props.active_hooks).unwrap_or(ActiveHooks::empty()))
+        .active_events(
+            ActiveEvents::from_bits(props.active_events).unwrap_or(ActiveEvents::empty()),
+        )
+        .active_collision_types(
+            ActiveCollisionTypes::from_bits(props.active_collision_types)
+                .unwrap_or(ActiveCollisionTypes::empty()),
+        )
+        .sensor(props.is_sensor)
+        .friction_combine_rule(props.friction_combine_rule)
+        .restitution_combine_rule(props.restitution_combine_rule)
+        .contact_force_event_threshold(props.contact_force_event_threshold)
+        .contact_skin(props.contact_skin);
+
+    let builder = match props.mass_props {
+        SceneMassProps::Density { density } => builder.density(density),
+        SceneMassProps::Mass { mass } => builder.mass(mass),
+        SceneMassProps::MassProps {
+            mass,
+            center_of_mass,
+            principal_angular_inertia,
+            #[cfg(feature = "dim3")]
+            angular_inertia_local_frame,
+        } => {
+            #[cfg(feature = "dim2")]
+            let mprops = MassProperties::new(center_of_mass, mass, principal_angular_inertia);
+            #[cfg(feature = "dim3")]
+            let mprops = MassProperties::with_principal_inertia_frame(
+                center_of_mass,
+                mass,
+                principal_angular_inertia,
+                rotation_from_scene(angular_inertia_local_frame)?,
+            );
+            builder.mass_properties(mprops)
+        }
+    };
+
+    Ok(builder)
+}
+
+#[wasm_bindgen]
+impl RawSerializationPipeline {
+    /// Exports the physics world as a JSON scene.
+    ///
+    /// Unlike snapshots, the scene only contains the parameters the rigid-bodies, colliders,
+    /// and joints would be created with (the same as the ones given to their builders), so it
+    /// can be written or edited by hand and doesn’t depend on the version of the library.
+    /// Contacts, islands, and the internal solver state are not exported.
+    ///
+    /// Throws an error if a collider has a compound or custom shape.
+    pub fn serializeScene(
+        &self,
+        gravity: &RawVector,
+        integrationParameters: &RawIntegrationParameters,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> Result<String, JsError> {
+        let mut body_ids = HashMap::new();
+        let mut scene = Scene {
+            version: SCENE_FORMAT_VERSION,
+            dim: DIM as u8,
+            gravity: gravity.0,
+            integration_parameters: SceneIntegrationParameters::new(&integrationParameters.0),
+            bodies: vec![],
+            colliders: vec![],
+            impulse_joints: vec![],
+            multibody_joints: vec![],
+        };
+
+        for (handle, _) in bodies.0.iter() {
+            body_ids.insert(handle, scene.bodies.len());
+            scene.bodies.push(scene_rigid_body(&bodies.0, handle));
+        }
+
+        for (_, co) in colliders.0.iter() {
+            let pos = co.position_wrt_parent().unwrap_or(co.position());
+            scene.colliders.push(SceneCollider {
+                shape: SceneShape::new(co.shared_shape())?,
+                parent: co.parent().map(|parent| body_ids[&parent]),
+                props: SceneColliderProps {
+                    enabled: co.is_enabled(),
+                    translation: pos.translation.vector,
+                    rotation: scene_rotation(&pos.rotation),
+                    mass_props: scene_mass_props(co),
+                    friction: co.friction(),
+                    restitution: co.restitution(),
+                    friction_combine_rule: co.friction_combine_rule(),
+                    restitution_combine_rule: co.restitution_combine_rule(),
+                    is_sensor: co.is_sensor(),
+                    collision_groups: crate::geometry::pack_interaction_groups(
+                        co.collision_groups(),
+                    ),
+                    solver_groups: crate::geometry::pack_interaction_groups(co.solver_groups()),
+                    active_collision_types: co.active_collision_types().bits(),
+                    active_hooks: co.active_hooks().bits(),
+                    active_events: co.active_events().bits(),
+                    contact_force_event_threshold: co.contact_force_event_threshold(),
+                    contact_skin: co.contact_skin(),
+                },
+            });
+        }
+
+        for (handle, joint) in impulse_joints.0.iter() {
+            // Infinite thresholds can’t be represented in JSON, so they are left out.
+            let threshold = impulse_joints.1.get(&handle.0);
+            scene.impulse_joints.push(SceneImpulseJoint {
+                body1: body_ids[&joint.body1],
+                body2: body_ids[&joint.body2],
+                break_force: threshold.map(|t| t.max_force).filter(|f| f.is_finite()),
+                break_torque: threshold.map(|t| t.max_torque).filter(|t| t.is_finite()),
+                data: SceneJointData::new(&joint.data),
+            });
+        }
+
+        for multibody in multibody_joints.0.multibodies() {
+            // Links are sorted so that parents always come before their children, which is a
+            // valid insertion order.
+            for link in multibody.links() {
+                if let Some(parent) = link.parent_id().and_then(|id| multibody.link(id)) {
+                    scene.multibody_joints.push(SceneMultibodyJoint {
+                        body1: body_ids[&parent.rigid_body_handle()],
+                        body2: body_ids[&link.rigid_body_handle()],
+                        kinematic: link.joint().kinematic,
+                        data: SceneJointData::new(&link.joint().data),
+                    });
+                }
+            }
+        }
+
+        serde_json::to_string_pretty(&scene)
+            .map_err(|e| JsError::new(&format!("Cannot export scene: {}", e)))
+    }
+
+    /// Creates a new physics world from a JSON scene created by `serializeScene` or written
+    /// by hand.
+    ///
+    /// Every field of the rigid-bodies, colliders, and joints is optional (except the shape
+    /// of colliders and the rigid-bodies attached to joints) and defaults to the same value
+    /// as for their builders.
+    ///
+    /// Throws an error if the scene is invalid or was written for a different dimension.
+    pub fn deserializeScene(&self, json: &str) -> Result<RawDeserializedWorld, JsError> {
+        let scene: Scene = serde_json::from_str(json).map_err(scene_error)?;
+
+        if scene.version > SCENE_FORMAT_VERSION {
+            return Err(JsError::new(&format!(
+                "Invalid scene: format version {} is not supported by this version of the library.",
+                scene.version
+            )));
+        }
+
+        if scene.dim as usize != DIM {
+            return Err(JsError::new(&format!(
+                "Invalid scene: it is a {}D scene but this is the {}D version of the library.",
+                scene.dim, DIM
+            )));
+        }
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut impulse_joint_break_thresholds = JointBreakThresholds::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+
+        let handles = scene
+            .bodies
+            .iter()
+            .map(|body| Ok(bodies.insert(build_rigid_body(body)?)))
+            .collect::<Result<Vec<_>, JsError>>()?;
+        let body_handle = |id: usize| {
+            handles
+                .get(id)
+                .copied()
+                .ok_or_else(|| scene_error(format!("rigid-body index {} out of bounds.", id)))
+        };
+
+        for collider in scene.colliders {
+            let co = build_collider(collider.shape.build()?, &collider.props)?;
+            match collider.parent {
+                Some(parent) => {
+                    colliders.insert_with_parent(co, body_handle(parent)?, &mut bodies);
+                }
+                None => {
+                    colliders.insert(co);
+                }
+            }
+        }
+
+        for joint in &scene.impulse_joints {
+            let (body1, body2) = (body_handle(joint.body1)?, body_handle(joint.body2)?);
+            let handle = impulse_joints.insert(body1, body2, joint.data.build()?, true);
+
+            if let Some(threshold) = joint.break_threshold() {
+                impulse_joint_break_thresholds.insert(handle.0, threshold);
+            }
+        }
+
+        for joint in &scene.multibody_joints {
+            let (body1, body2) = (body_handle(joint.body1)?, body_handle(joint.body2)?);
+            let data = joint.data.build()?;
+            let inserted = if joint.kinematic {
+                multibody_joints.insert_kinematic(body1, body2, data, true)
+            } else {
+                multibody_joints.insert(body1, body2, data, true)
+            };
+
+            if inserted.is_none() {
+                return Err(scene_error(format!(
+                    "the multibody joint between rigid-bodies {} and {} would create a loop \
+                     or give rigid-body {} two parents.",
+                    joint.body1, joint.body2, joint.body2
+                )));
+            }
+        }
+
+        let world = DeserializableWorld {
+            gravity: scene.gravity,
+            integration_parameters: scene.integration_parameters.build(),
+            islands: IslandManager::new(),
+            broad_phase: DefaultBroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            bodies,
+            colliders,
+            impulse_joints,
+            multibody_joints,
+            impulse_joint_break_thresholds,
+        };
+        Ok(world.into_raw())
+    }
+}
@@ -1,41 +1,193 @@
 use crate::dynamics::{
-    RawImpulseJointSet, RawIntegrationParameters, RawIslandManager, RawMultibodyJointSet,
-    RawRigidBodySet,
+    JointBreakThreshold, JointBreakThresholds, RawImpulseJointSet, RawIntegrationParameters,
+    RawIslandManager, RawMultibodyJointSet, RawRigidBodySet,
 };
 use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
 use crate::math::RawVector;
+use crate::utils::{self, FlatHandle};
 use js_sys::Uint8Array;
 use rapier::dynamics::{
-    ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBodySet,
+    ImpulseJoint, ImpulseJointHandle, ImpulseJointSet, IntegrationParameters, IslandManager,
+    MultibodyJoint, MultibodyJointHandle, MultibodyJointSet, RigidBody, RigidBodyHandle,
+    RigidBodySet,
 };
-use rapier::geometry::{ColliderSet, DefaultBroadPhase, NarrowPhase};
-use rapier::math::Vector;
+use rapier::geometry::{Collider, ColliderHandle, ColliderSet, DefaultBroadPhase, NarrowPhase};
+use rapier::math::{Vector, DIM};
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::prelude::*;
 
+/// Magic number at the beginning of every snapshot.
+pub(crate) const SNAPSHOT_MAGIC: [u8; 4] = *b"RPJS";
+/// Magic number at the beginning of every prefab created by `serializePrefab`.
+const PREFAB_MAGIC: [u8; 4] = *b"RPJP";
+/// Version of the snapshot format.
+///
+/// The magic number and this version are always written as the first 8 bytes of a snapshot
+/// so that future versions can identify the layout of the rest of the data. This must be
+/// incremented whenever the layout of `SnapshotHeader` or of the serialized world changes.
+const SNAPSHOT_FORMAT_VERSION: u32 = 3;
+
+/// Bit set in `SnapshotHeader::features` for builds with enhanced cross-platform determinism.
+const FEATURE_ENHANCED_DETERMINISM: u32 = 1 << 0;
+/// Bit set in `SnapshotHeader::features` for builds with SIMD optimizations.
+const FEATURE_SIMD: u32 = 1 << 1;
+
+/// Information about the build that created a snapshot.
+///
+/// The serialized world relies on the internal data-structures of the physics engine, which may
+/// change with any release. A snapshot can only be restored by the exact same version and flavor
+/// (dimension and feature set) of the library.
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct SnapshotHeader {
+    /// The version of the `rapier` crate these bindings were built with.
+    rapier_version: String,
+    /// The version of these JS bindings, which serialize some data of their own.
+    bindings_version: String,
+    dim: u8,
+    features: u32,
+}
+
+impl SnapshotHeader {
+    pub(crate) fn current() -> Self {
+        let mut features = 0;
+        if cfg!(feature = "enhanced-determinism") {
+            features |= FEATURE_ENHANCED_DETERMINISM;
+        }
+        if cfg!(feature = "simd-stable") {
+            features |= FEATURE_SIMD;
+        }
+
+        Self {
+            rapier_version: rapier::VERSION.to_string(),
+            bindings_version: env!("CARGO_PKG_VERSION").to_string(),
+            dim: DIM as u8,
+            features,
+        }
+    }
+
+    fn flavor(&self) -> String {
+        let mut result = format!("rapier{}d", self.dim);
+        if self.features & FEATURE_ENHANCED_DETERMINISM != 0 {
+            result += "-deterministic";
+        }
+        if self.features & FEATURE_SIMD != 0 {
+            result += "-simd";
+        }
+        result
+    }
+
+    /// Writes the given magic number, the snapshot format version, and the header of the
+    /// current build.
+    pub(crate) fn write(magic: [u8; 4], out: &mut Vec<u8>) -> bincode::Result<()> {
+        out.extend_from_slice(&magic);
+        out.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+        bincode::serialize_into(out, &Self::current())
+    }
+
+    /// Reads and validates the header, starting with the given magic number, at the beginning
+    /// of `data`.
+    ///
+    /// Returns the remaining data (after the header) if the snapshot was created by the
+    /// same version and flavor as the current build, or a human-readable error otherwise.
+    pub(crate) fn read(magic: [u8; 4], data: &[u8]) -> Result<&[u8], JsError> {
+        if data.len() < 8 || data[0..4] != magic {
+            return Err(JsError::new(
+                "Invalid snapshot: missing header. The data is either corrupted, not a snapshot, or \
+                 a snapshot created by an older version of Rapier that didn’t version its snapshots.",
+            ));
+        }
+
+        let format_version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        if format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(JsError::new(&format!(
+                "Incompatible snapshot: unsupported snapshot format version {} (expected {}).",
+                format_version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+
+        let mut reader = &data[8..];
+        let header: Self = bincode::deserialize_from(&mut reader)
+            .map_err(|e| JsError::new(&format!("Invalid snapshot header: {}", e)))?;
+        let current = Self::current();
+
+        if header != current {
+            return Err(JsError::new(&format!(
+                "Incompatible snapshot: created by {} {} (JS bindings version {}), but this is \
+                 {} {} (JS bindings version {}). Snapshots can only be restored by the exact same \
+                 version and flavor of Rapier and of its JS bindings.",
+                header.flavor(),
+                header.rapier_version,
+                header.bindings_version,
+                current.flavor(),
+                current.rapier_version,
+                current.bindings_version,
+            )));
+        }
+
+        Ok(reader)
+    }
+}
+
 #[derive(Serialize)]
-struct SerializableWorld<'a> {
-    gravity: &'a Vector<f32>,
-    integration_parameters: &'a IntegrationParameters,
-    islands: &'a IslandManager,
-    broad_phase: &'a DefaultBroadPhase,
-    narrow_phase: &'a NarrowPhase,
-    bodies: &'a RigidBodySet,
-    colliders: &'a ColliderSet,
-    impulse_joints: &'a ImpulseJointSet,
-    multibody_joints: &'a MultibodyJointSet,
+pub(crate) struct SerializableWorld<'a> {
+    pub(crate) gravity: &'a Vector<f32>,
+    pub(crate) integration_parameters: &'a IntegrationParameters,
+    pub(crate) islands: &'a IslandManager,
+    pub(crate) broad_phase: &'a DefaultBroadPhase,
+    pub(crate) narrow_phase: &'a NarrowPhase,
+    pub(crate) bodies: &'a RigidBodySet,
+    pub(crate) colliders: &'a ColliderSet,
+    pub(crate) impulse_joints: &'a ImpulseJointSet,
+    pub(crate) multibody_joints: &'a MultibodyJointSet,
+    pub(crate) impulse_joint_break_thresholds: &'a JointBreakThresholds,
 }
 
 #[derive(Deserialize)]
-struct DeserializableWorld {
-    gravity: Vector<f32>,
-    integration_parameters: IntegrationParameters,
-    islands: IslandManager,
-    broad_phase: DefaultBroadPhase,
-    narrow_phase: NarrowPhase,
-    bodies: RigidBodySet,
-    colliders: ColliderSet,
-    impulse_joints: ImpulseJointSet,
-    multibody_joints: MultibodyJointSet,
+pub(crate) struct DeserializableWorld {
+    pub(crate) gravity: Vector<f32>,
+    pub(crate) integration_parameters: IntegrationParameters,
+    pub(crate) islands: IslandManager,
+    pub(crate) broad_phase: DefaultBroadPhase,
+    pub(crate) narrow_phase: NarrowPhase,
+    pub(crate) bodies: RigidBodySet,
+    pub(crate) colliders: ColliderSet,
+    pub(crate) impulse_joints: ImpulseJointSet,
+    pub(crate) multibody_joints: MultibodyJointSet,
+    pub(crate) impulse_joint_break_thresholds: JointBreakThresholds,
+}
+
+impl DeserializableWorld {
+    pub(crate) fn as_serializable(&self) -> SerializableWorld<'_> {
+        SerializableWorld {
+            gravity: &self.gravity,
+            integration_parameters: &self.integration_parameters,
+            islands: &self.islands,
+            broad_phase: &self.broad_phase,
+            narrow_phase: &self.narrow_phase,
+            bodies: &self.bodies,
+            colliders: &self.colliders,
+            impulse_joints: &self.impulse_joints,
+            multibody_joints: &self.multibody_joints,
+            impulse_joint_break_thresholds: &self.impulse_joint_break_thresholds,
+        }
+    }
+
+    pub(crate) fn into_raw(self) -> RawDeserializedWorld {
+        RawDeserializedWorld {
+            gravity: Some(RawVector(self.gravity)),
+            integrationParameters: Some(RawIntegrationParameters(self.integration_parameters)),
+            islands: Some(RawIslandManager(self.islands)),
+            broadPhase: Some(RawBroadPhase(self.broad_phase)),
+            narrowPhase: Some(RawNarrowPhase(self.narrow_phase)),
+            bodies: Some(RawRigidBodySet(self.bodies)),
+            colliders: Some(RawColliderSet(self.colliders)),
+            impulse_joints: Some(RawImpulseJointSet(
+                self.impulse_joints,
+                self.impulse_joint_break_thresholds,
+            )),
+            multibody_joints: Some(RawMultibodyJointSet(self.multibody_joints)),
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -90,6 +242,59 @@ impl RawDeserializedWorld {
     }
 }
 
+/// A subset of the rigid-bodies of a world, with their colliders and the joints between them.
+///
+/// The handles are the ones the objects had when the prefab was created. They are only used to
+/// reconnect the objects together, and to report the handle remapping when the prefab is inserted.
+#[derive(Serialize, Deserialize)]
+struct Prefab {
+    bodies: Vec<(RigidBodyHandle, RigidBody)>,
+    colliders: Vec<(ColliderHandle, RigidBodyHandle, Collider)>,
+    /// The impulse joints, with their break threshold if they are breakable.
+    impulse_joints: Vec<(
+        ImpulseJointHandle,
+        ImpulseJoint,
+        Option<JointBreakThreshold>,
+    )>,
+    multibody_joints: Vec<(RigidBodyHandle, RigidBodyHandle, MultibodyJoint)>,
+}
+
+/// The handles of the objects created by `RawSerializationPipeline.insertPrefab`.
+///
+/// Each array contains pairs of handles `[old handle, new handle]` where the old handle is the
+/// handle the object had when the prefab was created, and the new handle is the handle of the
+/// inserted object.
+#[wasm_bindgen]
+pub struct RawPrefabHandleMap {
+    bodies: Vec<FlatHandle>,
+    colliders: Vec<FlatHandle>,
+    impulse_joints: Vec<FlatHandle>,
+    multibody_joints: Vec<FlatHandle>,
+}
+
+#[wasm_bindgen]
+impl RawPrefabHandleMap {
+    /// The `[old handle, new handle]` pairs of the inserted rigid-bodies.
+    pub fn bodies(&self) -> Vec<FlatHandle> {
+        self.bodies.clone()
+    }
+
+    /// The `[old handle, new handle]` pairs of the inserted colliders.
+    pub fn colliders(&self) -> Vec<FlatHandle> {
+        self.colliders.clone()
+    }
+
+    /// The `[old handle, new handle]` pairs of the inserted impulse joints.
+    pub fn impulseJoints(&self) -> Vec<FlatHandle> {
+        self.impulse_joints.clone()
+    }
+
+    /// The `[old handle, new handle]` pairs of the inserted multibody joints.
+    pub fn multibodyJoints(&self) -> Vec<FlatHandle> {
+        self.multibody_joints.clone()
+    }
+}
+
 #[wasm_bindgen]
 pub struct RawSerializationPipeline;
 
@@ -122,24 +327,187 @@ impl RawSerializationPipeline {
             colliders: &colliders.0,
             impulse_joints: &impulse_joints.0,
             multibody_joints: &multibody_joints.0,
+            impulse_joint_break_thresholds: &impulse_joints.1,
         };
-        let snap = bincode::serialize(&to_serialize).ok()?;
+        let mut snap = vec![];
+        SnapshotHeader::write(SNAPSHOT_MAGIC, &mut snap).ok()?;
+        bincode::serialize_into(&mut snap, &to_serialize).ok()?;
         Some(Uint8Array::from(&snap[..]))
     }
 
-    pub fn deserializeAll(&self, data: Uint8Array) -> Option<RawDeserializedWorld> {
+    /// Deserializes a snapshot created by `serializeAll`.
+    ///
+    /// Throws an error if the snapshot is invalid, or if it was created by a different version
+    /// or flavor (dimension and feature set) of the library.
+    pub fn deserializeAll(&self, data: Uint8Array) -> Result<RawDeserializedWorld, JsError> {
+        let data = data.to_vec();
+        let payload = SnapshotHeader::read(SNAPSHOT_MAGIC, &data)?;
+        let d: DeserializableWorld = bincode::deserialize(payload)
+            .map_err(|e| JsError::new(&format!("Invalid snapshot: {}", e)))?;
+        Ok(d.into_raw())
+    }
+
+    /// Serializes the given rigid-bodies, their colliders, and the joints between them into a
+    /// prefab that can be inserted into any world with `insertPrefab`.
+    ///
+    /// Unlike `serializeAll`, this doesn’t serialize the broad-phase, narrow-phase, and
+    /// island manager. Joints attached to a rigid-body that isn’t part of `handles` are ignored.
+    ///
+    /// Throws an error if one of the handles doesn’t match any rigid-body.
+    ///
+    /// # Parameters
+    /// - `handles`: the handles of the rigid-bodies to serialize.
+    pub fn serializePrefab(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+        handles: Vec<FlatHandle>,
+    ) -> Result<Uint8Array, JsError> {
+        let mut selected = HashSet::new();
+        let handles: Vec<_> = handles
+            .into_iter()
+            .map(utils::body_handle)
+            .filter(|handle| selected.insert(*handle))
+            .collect();
+
+        let mut prefab = Prefab {
+            bodies: vec![],
+            colliders: vec![],
+            impulse_joints: vec![],
+            multibody_joints: vec![],
+        };
+
+        for handle in handles {
+            let rb = bodies.0.get(handle).ok_or_else(|| {
+                JsError::new(&format!(
+                    "Cannot serialize prefab: invalid rigid-body handle {}.",
+                    utils::flat_handle(handle.0)
+                ))
+            })?;
+            prefab.bodies.push((handle, rb.clone()));
+
+            for co_handle in rb.colliders() {
+                if let Some(co) = colliders.0.get(*co_handle) {
+                    prefab.colliders.push((*co_handle, handle, co.clone()));
+                }
+            }
+
+            // Each joint is visited from both its attached rigid-bodies, only keep it once.
+            for (_, _, joint_handle, joint) in impulse_joints.0.attached_joints(handle) {
+                if joint.body1 == handle && selected.contains(&joint.body2) {
+                    let threshold = impulse_joints.1.get(&joint_handle.0).copied();
+                    prefab
+                        .impulse_joints
+                        .push((joint_handle, joint.clone(), threshold));
+                }
+            }
+
+            // The multibody joint attaching a rigid-body to its parent has the same handle as
+            // the rigid-body.
+            if let Some((mb, link_id)) = multibody_joints.0.get(MultibodyJointHandle(handle.0)) {
+                let link = mb.link(link_id);
+                let parent = link.and_then(|link| mb.link(link.parent_id()?));
+                if let (Some(link), Some(parent)) = (link, parent) {
+                    if selected.contains(&parent.rigid_body_handle()) {
+                        prefab.multibody_joints.push((
+                            parent.rigid_body_handle(),
+                            handle,
+                            link.joint,
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut data = vec![];
+        SnapshotHeader::write(PREFAB_MAGIC, &mut data)
+            .and_then(|_| bincode::serialize_into(&mut data, &prefab))
+            .map_err(|e| JsError::new(&format!("Cannot serialize prefab: {}", e)))?;
+        Ok(Uint8Array::from(&data[..]))
+    }
+
+    /// Inserts the rigid-bodies, colliders, and joints of a prefab created by `serializePrefab`
+    /// into the given sets.
+    ///
+    /// The inserted objects are given new handles. Multibody joints are inserted with their
+    /// initial (zero) configuration.
+    ///
+    /// Throws an error if the prefab is invalid, or if it was created by a different version
+    /// or flavor (dimension and feature set) of the library.
+    pub fn insertPrefab(
+        &self,
+        data: Uint8Array,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulse_joints: &mut RawImpulseJointSet,
+        multibody_joints: &mut RawMultibodyJointSet,
+    ) -> Result<RawPrefabHandleMap, JsError> {
         let data = data.to_vec();
-        let d: DeserializableWorld = bincode::deserialize(&data[..]).ok()?;
-        Some(RawDeserializedWorld {
-            gravity: Some(RawVector(d.gravity)),
-            integrationParameters: Some(RawIntegrationParameters(d.integration_parameters)),
-            islands: Some(RawIslandManager(d.islands)),
-            broadPhase: Some(RawBroadPhase(d.broad_phase)),
-            narrowPhase: Some(RawNarrowPhase(d.narrow_phase)),
-            bodies: Some(RawRigidBodySet(d.bodies)),
-            colliders: Some(RawColliderSet(d.colliders)),
-            impulse_joints: Some(RawImpulseJointSet(d.impulse_joints)),
-            multibody_joints: Some(RawMultibodyJointSet(d.multibody_joints)),
-        })
+        let payload = SnapshotHeader::read(PREFAB_MAGIC, &data)?;
+        let prefab: Prefab = bincode::deserialize(payload)
+            .map_err(|e| JsError::new(&format!("Invalid prefab: {}", e)))?;
+
+        let mut body_map = HashMap::new();
+        let mut result = RawPrefabHandleMap {
+            bodies: vec![],
+            colliders: vec![],
+            impulse_joints: vec![],
+            multibody_joints: vec![],
+        };
+
+        for (old_handle, rb) in prefab.bodies {
+            let new_handle = bodies.0.insert(rb);
+            body_map.insert(old_handle, new_handle);
+            result.bodies.push(utils::flat_handle(old_handle.0));
+            result.bodies.push(utils::flat_handle(new_handle.0));
+        }
+
+        for (old_handle, parent, co) in prefab.colliders {
+            if let Some(parent) = body_map.get(&parent) {
+                let new_handle = colliders.0.insert_with_parent(co, *parent, &mut bodies.0);
+                result.colliders.push(utils::flat_handle(old_handle.0));
+                result.colliders.push(utils::flat_handle(new_handle.0));
+            }
+        }
+
+        for (old_handle, joint, threshold) in prefab.impulse_joints {
+            if let (Some(body1), Some(body2)) =
+                (body_map.get(&joint.body1), body_map.get(&joint.body2))
+            {
+                let new_handle = impulse_joints.0.insert(*body1, *body2, joint.data, false);
+                // Keep the impulses for warm-starting.
+                if let Some(new_joint) = impulse_joints.0.get_mut(new_handle, false) {
+                    new_joint.impulses = joint.impulses;
+                }
+                if let Some(threshold) = threshold {
+                    impulse_joints.1.insert(new_handle.0, threshold);
+                }
+                result.impulse_joints.push(utils::flat_handle(old_handle.0));
+                result.impulse_joints.push(utils::flat_handle(new_handle.0));
+            }
+        }
+
+        for (parent, child, joint) in prefab.multibody_joints {
+            if let (Some(body1), Some(body2)) = (body_map.get(&parent), body_map.get(&child)) {
+                let new_handle = if joint.kinematic {
+                    multibody_joints
+                        .0
+                        .insert_kinematic(*body1, *body2, joint.data, false)
+                } else {
+                    multibody_joints.0.insert(*body1, *body2, joint.data, false)
+                };
+
+                if let Some(new_handle) = new_handle {
+                    result.multibody_joints.push(utils::flat_handle(child.0));
+                    result
+                        .multibody_joints
+                        .push(utils::flat_handle(new_handle.0));
+                }
+            }
+        }
+
+        Ok(result)
     }
 }
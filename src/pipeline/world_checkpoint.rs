@@ -0,0 +1,127 @@
+use crate::dynamics::{
+    JointBreakThresholds, RawImpulseJointSet, RawIntegrationParameters, RawIslandManager,
+    RawMultibodyJointSet, RawRigidBodySet,
+};
+use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
+use rapier::dynamics::{
+    ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBodySet,
+};
+use rapier::geometry::{ColliderSet, DefaultBroadPhase, NarrowPhase};
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone)]
+struct CheckpointState {
+    integration_parameters: IntegrationParameters,
+    islands: IslandManager,
+    broad_phase: DefaultBroadPhase,
+    narrow_phase: NarrowPhase,
+    bodies: RigidBodySet,
+    colliders: ColliderSet,
+    impulse_joints: ImpulseJointSet,
+    impulse_joint_break_thresholds: JointBreakThresholds,
+    multibody_joints: MultibodyJointSet,
+}
+
+/// A copy of the physics state of a world, kept in the WASM memory.
+///
+/// Unlike snapshots, saving and restoring a checkpoint doesn’t involve any serialization nor
+/// any copy to or from JS.
+#[wasm_bindgen]
+pub struct RawWorldCheckpoint {
+    state: Option<CheckpointState>,
+}
+
+impl Default for RawWorldCheckpoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl RawWorldCheckpoint {
+    /// Creates a new empty checkpoint.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self { state: None }
+    }
+
+    /// Is this checkpoint empty, i.e., was `save` never called on it?
+    pub fn isEmpty(&self) -> bool {
+        self.state.is_none()
+    }
+
+    /// Saves a deep copy of the given physics state into this checkpoint, replacing the
+    /// previously saved state (if any).
+    ///
+    /// The memory allocated for the previously saved state is reused whenever possible.
+    pub fn save(
+        &mut self,
+        integrationParameters: &RawIntegrationParameters,
+        islands: &RawIslandManager,
+        broadPhase: &RawBroadPhase,
+        narrowPhase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) {
+        if let Some(state) = &mut self.state {
+            state.integration_parameters = integrationParameters.0;
+            state.islands.clone_from(&islands.0);
+            state.broad_phase.clone_from(&broadPhase.0);
+            state.narrow_phase.clone_from(&narrowPhase.0);
+            state.bodies.clone_from(&bodies.0);
+            state.colliders.clone_from(&colliders.0);
+            state.impulse_joints.clone_from(&impulse_joints.0);
+            state
+                .impulse_joint_break_thresholds
+                .clone_from(&impulse_joints.1);
+            state.multibody_joints.clone_from(&multibody_joints.0);
+        } else {
+            self.state = Some(CheckpointState {
+                integration_parameters: integrationParameters.0,
+                islands: islands.0.clone(),
+                broad_phase: broadPhase.0.clone(),
+                narrow_phase: narrowPhase.0.clone(),
+                bodies: bodies.0.clone(),
+                colliders: colliders.0.clone(),
+                impulse_joints: impulse_joints.0.clone(),
+                impulse_joint_break_thresholds: impulse_joints.1.clone(),
+                multibody_joints: multibody_joints.0.clone(),
+            });
+        }
+    }
+
+    /// Overwrites the given physics state with a deep copy of the state saved in this checkpoint.
+    ///
+    /// The checkpoint is left unchanged so it can be restored several times. Returns `false`
+    /// (and doesn’t modify anything) if this checkpoint is empty.
+    pub fn restore(
+        &self,
+        integrationParameters: &mut RawIntegrationParameters,
+        islands: &mut RawIslandManager,
+        broadPhase: &mut RawBroadPhase,
+        narrowPhase: &mut RawNarrowPhase,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulse_joints: &mut RawImpulseJointSet,
+        multibody_joints: &mut RawMultibodyJointSet,
+    ) -> bool {
+        let Some(state) = &self.state else {
+            return false;
+        };
+
+        integrationParameters.0 = state.integration_parameters;
+        islands.0.clone_from(&state.islands);
+        broadPhase.0.clone_from(&state.broad_phase);
+        narrowPhase.0.clone_from(&state.narrow_phase);
+        bodies.0.clone_from(&state.bodies);
+        colliders.0.clone_from(&state.colliders);
+        impulse_joints.0.clone_from(&state.impulse_joints);
+        impulse_joints
+            .1
+            .clone_from(&state.impulse_joint_break_thresholds);
+        multibody_joints.0.clone_from(&state.multibody_joints);
+        true
+    }
+}
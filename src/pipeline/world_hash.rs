@@ -0,0 +1,132 @@
+use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::utils::{self, FlatHandle};
+use rapier::data::Index;
+use rapier::dynamics::RigidBody;
+use rapier::math::Isometry;
+use wasm_bindgen::prelude::*;
+
+/// Computes an FNV-1a hash of the exact bits of the values written into it.
+struct StateHasher(u64);
+
+impl StateHasher {
+    fn new() -> Self {
+        Self(utils::FNV1A_INIT)
+    }
+
+    fn write_handle(&mut self, handle: Index) {
+        self.0 = utils::fnv1a(self.0, &utils::flat_handle(handle).to_le_bytes());
+    }
+
+    fn write_floats(&mut self, values: impl IntoIterator<Item = f32>) {
+        for value in values {
+            self.0 = utils::fnv1a(self.0, &value.to_le_bytes());
+        }
+    }
+
+    fn write_isometry(&mut self, pos: &Isometry<f32>) {
+        self.write_floats(pos.translation.vector.iter().copied());
+        #[cfg(feature = "dim2")]
+        self.write_floats([pos.rotation.re, pos.rotation.im]);
+        #[cfg(feature = "dim3")]
+        self.write_floats(pos.rotation.coords.iter().copied());
+    }
+
+    fn write_rigid_body(&mut self, handle: Index, rb: &RigidBody) {
+        self.write_handle(handle);
+        self.write_isometry(rb.position());
+        self.write_floats(rb.linvel().iter().copied());
+        #[cfg(feature = "dim2")]
+        self.write_floats([rb.angvel()]);
+        #[cfg(feature = "dim3")]
+        self.write_floats(rb.angvel().iter().copied());
+    }
+}
+
+/// A hash of the state of a physics world, computed by `RawWorldStateHash.new`.
+///
+/// The hash is computed from the exact bits of the rigid-body poses and velocities, collider
+/// poses, and joint states, in handle order. Two worlds simulated with the deterministic builds
+/// of the library have the same hash if and only if (barring hash collisions) their states
+/// are identical.
+#[wasm_bindgen]
+pub struct RawWorldStateHash {
+    hash: u64,
+    body_handles: Vec<FlatHandle>,
+    body_hashes: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl RawWorldStateHash {
+    /// Computes the hash of the given physics state.
+    ///
+    /// # Parameters
+    /// - `perBodyHashes`: if `true`, the hash of each rigid-body’s pose and velocities is also
+    ///   computed separately, allowing to identify the rigid-bodies responsible for a hash mismatch.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+        perBodyHashes: bool,
+    ) -> Self {
+        let mut hasher = StateHasher::new();
+        let mut body_handles = vec![];
+        let mut body_hashes = vec![];
+
+        for (handle, rb) in bodies.0.iter() {
+            hasher.write_rigid_body(handle.0, rb);
+
+            if perBodyHashes {
+                let mut body_hasher = StateHasher::new();
+                body_hasher.write_rigid_body(handle.0, rb);
+                body_handles.push(utils::flat_handle(handle.0));
+                body_hashes.push(body_hasher.0 as u32);
+                body_hashes.push((body_hasher.0 >> 32) as u32);
+            }
+        }
+
+        for (handle, co) in colliders.0.iter() {
+            hasher.write_handle(handle.0);
+            hasher.write_isometry(co.position());
+        }
+
+        for (handle, joint) in impulse_joints.0.iter() {
+            hasher.write_handle(handle.0);
+            hasher.write_handle(joint.body1.0);
+            hasher.write_handle(joint.body2.0);
+            hasher.write_floats(joint.impulses.iter().copied());
+        }
+
+        for multibody in multibody_joints.0.multibodies() {
+            for link in multibody.links() {
+                hasher.write_handle(link.rigid_body_handle().0);
+                hasher.write_isometry(&link.joint().body_to_parent());
+            }
+            hasher.write_floats(multibody.generalized_velocity().iter().copied());
+        }
+
+        Self {
+            hash: hasher.0,
+            body_handles,
+            body_hashes,
+        }
+    }
+
+    /// The hash of the whole physics state, as a 16-digits hexadecimal string.
+    pub fn hash(&self) -> String {
+        format!("{:016x}", self.hash)
+    }
+
+    /// The handles of the rigid-bodies for which a separate hash was computed.
+    pub fn bodyHandles(&self) -> Vec<FlatHandle> {
+        self.body_handles.clone()
+    }
+
+    /// The separate hash of each rigid-body, as two consecutive 32-bits integers (lower bits
+    /// first) per rigid-body, in the same order as `bodyHandles`.
+    pub fn bodyHashes(&self) -> Vec<u32> {
+        self.body_hashes.clone()
+    }
+}
@@ -31,6 +31,21 @@ pub fn flat_handle(id: Index) -> FlatHandle {
     FlatHandle::from_bits(i as u64 | ((g as u64) << 32))
 }
 
+/// The initial value of an FNV-1a hash, to be passed to the first call to `fnv1a`.
+pub(crate) const FNV1A_INIT: u64 = 0xcbf29ce484222325;
+
+/// Combines `hash` with the given bytes using the 64-bits FNV-1a hash function.
+///
+/// Unlike the hashers from the standard library, the result is guaranteed to be stable across
+/// platforms and versions.
+pub(crate) fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
 // pub type FlatHandle = u32;
 //
 // #[inline(always)]